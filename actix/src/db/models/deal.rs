@@ -47,6 +47,35 @@ impl Deal {
 			.collection("deals")
 			.find_one(doc! {"_id": id}, None)?
 			.ok_or(Error::DealNotFound)?;
-		Ok(bson::from_bson(bson::Bson::Document(doc))?)
+		match bson::from_bson(bson::Bson::Document(doc.clone())) {
+			Ok(deal) => Ok(deal),
+			Err(_) => Self::repair(mc, doc),
+		}
+	}
+
+	// deals stored before every hand had 13 cards are given the cards left out
+	// and stored again, so each is only repaired once
+	fn repair(mc: mongo::Client, doc: bson::Document) -> Result<Self> {
+		#[derive(Deserialize)]
+		struct Legacy {
+			#[serde(rename = "_id")]
+			id: ObjectId,
+			deal: core::LegacyDeal,
+		}
+
+		let legacy: Legacy = bson::from_bson(bson::Bson::Document(doc))?;
+		let deal = Deal {
+			id: legacy.id,
+			deal: legacy.deal.repair()?,
+		};
+		let ser = bson::to_bson(&deal)?;
+		if let bson::Bson::Document(doc) = ser {
+			mc.database("bridge")
+				.collection("deals")
+				.replace_one(doc! {"_id": deal.id.clone()}, doc, None)?;
+		} else {
+			unreachable!("a deal should never deserialize into a non-doc bson value");
+		}
+		Ok(deal)
 	}
 }
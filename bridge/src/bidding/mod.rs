@@ -1,4 +1,6 @@
 use super::game::{Bid, BidSequence, Deck, Hand, Seat, Suit, Vulnerability};
+use bridge_core::{self, auction::Auction, board::Board};
+use bridge_system::System;
 use diesel::{delete, insert_into, prelude::*, sql_query, sql_types};
 use failure::Error;
 use std::{
@@ -238,13 +240,14 @@ fn bid_interactively(deal: &Deal, exercise: &Exercise) -> Result<()> {
     // check if we are logged in
     let user = current_user()?;
 
-    // print the deal and exercise
+    // print the deal and exercise, with what partner has shown so far
     let next_seat = exercise.bids.next_seat(deal.dealer);
     println!(
-        "{}{}{}",
+        "{}{}Partner has shown: {}\n{}",
         deal.header(),
         deal.view_for_seat(next_seat),
-        exercise
+        partner_shown(deal, exercise)?,
+        exercise.table(deal.dealer)
     );
 
     // prompt the user to bid on it
@@ -272,11 +275,30 @@ fn bid_interactively(deal: &Deal, exercise: &Exercise) -> Result<()> {
     Ok(())
 }
 
+// what the partner of the seat to bid has shown by their calls, as the
+// auction reads in standard american. The game types here print the same
+// way as bridge_core's, so they are carried over as text.
+fn partner_shown(deal: &Deal, exercise: &Exercise) -> Result<String> {
+    let core_error = |e: bridge_core::Error| format_err!("{}", e);
+    let dealer = bridge_core::Seat::parse(&deal.dealer.to_string()).map_err(core_error)?;
+    let vulnerable =
+        bridge_core::Vulnerability::parse(&deal.vulnerable.to_string()).map_err(core_error)?;
+    let auction =
+        Auction::parse(&exercise.bids.to_string(), dealer, vulnerable).map_err(core_error)?;
+    let system = System::sayc();
+    let inference = system.infer(&auction).map_err(core_error)?;
+    Ok(inference.shown(auction.next_seat().partner()).to_string())
+}
+
 fn generate_deal() -> Result<Deal> {
     use self::schema::deals::dsl::*;
 
+    // deals are numbered as boards in the order they are made, so the
+    // dealer and vulnerability go round as they do at the table
+    let made: i64 = deals.count().get_result(&connect_db()?)?;
+    let board = Board::new(made as u32 + 1).map_err(|e| format_err!("{}", e))?;
     insert_into(deals)
-        .values(Deal::random())
+        .values(Deal::random(board))
         .execute(&connect_db()?)?;
 
     let deal = deals.order(id.desc()).first(&connect_db()?)?;
@@ -436,11 +458,27 @@ impl Exercise {
             resolution: None,
         })
     }
+
+    // the auction table of the exercise's calls, which need the deal's dealer
+    // to line up under the right seats
+    pub fn table(&self, dealer: Seat) -> ExerciseTable {
+        ExerciseTable {
+            bids: &self.bids,
+            dealer,
+        }
+    }
+}
+
+// the exercise's calls in the auction table, with the dealer's first call
+// under the dealer
+pub struct ExerciseTable<'a> {
+    bids: &'a BidSequence,
+    dealer: Seat,
 }
 
-impl fmt::Display for Exercise {
+impl<'a> fmt::Display for ExerciseTable<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.bids.fmt_table(f, Seat::North)
+        self.bids.fmt_table(f, self.dealer)
     }
 }
 
@@ -467,11 +505,12 @@ pub struct DealInsert {
 }
 
 impl Deal {
-    pub fn random() -> DealInsert {
+    // a random deal with the dealer and vulnerability of the board
+    pub fn random(board: Board) -> DealInsert {
         let hands = Deck::deal();
         DealInsert {
-            dealer: Seat::North,
-            vulnerable: Vulnerability::Neither,
+            dealer: board.dealer().into(),
+            vulnerable: board.vulnerability().into(),
             north: hands.0,
             east: hands.1,
             south: hands.2,
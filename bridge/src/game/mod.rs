@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+use bridge_core;
 use diesel::{
     backend::Backend,
     deserialize::{self, FromSql},
@@ -62,6 +63,17 @@ impl Vulnerability {
     }
 }
 
+impl From<bridge_core::Vulnerability> for Vulnerability {
+    fn from(vulnerable: bridge_core::Vulnerability) -> Vulnerability {
+        match vulnerable {
+            bridge_core::Vulnerability::NS => Vulnerability::NS,
+            bridge_core::Vulnerability::EW => Vulnerability::EW,
+            bridge_core::Vulnerability::Both => Vulnerability::Both,
+            bridge_core::Vulnerability::Neither => Vulnerability::Neither,
+        }
+    }
+}
+
 impl fmt::Display for Vulnerability {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Vulnerability::*;
@@ -123,6 +135,17 @@ impl Seat {
     }
 }
 
+impl From<bridge_core::Seat> for Seat {
+    fn from(seat: bridge_core::Seat) -> Seat {
+        match seat {
+            bridge_core::Seat::North => Seat::North,
+            bridge_core::Seat::East => Seat::East,
+            bridge_core::Seat::South => Seat::South,
+            bridge_core::Seat::West => Seat::West,
+        }
+    }
+}
+
 impl fmt::Display for Seat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Seat::*;
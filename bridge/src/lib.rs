@@ -8,6 +8,8 @@ pub extern crate clap;
 #[macro_use]
 pub extern crate failure;
 pub extern crate rand;
+extern crate bridge_core;
+extern crate bridge_system;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
@@ -0,0 +1,157 @@
+//! An auction at the table: the calls together with who dealt and who is
+//! vulnerable. A `BidSequence` is just the calls, so anything that needs to
+//! know whose turn it is or who made a call has to be told the dealer; an
+//! `Auction` carries it, so each call is always seen with its seat.
+
+use super::{Annotation, AuctionResult, Bid, BidSequence, Deal, Result, Seat, Side, Vulnerability};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Auction {
+	dealer: Seat,
+	vulnerable: Vulnerability,
+	bids: BidSequence,
+}
+
+// one call of an auction and the seat that made it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Call<'a> {
+	pub seat: Seat,
+	pub bid: Bid,
+	pub annotation: &'a Annotation,
+}
+
+impl Auction {
+	pub fn new(dealer: Seat, vulnerable: Vulnerability) -> Self {
+		Self::from_bids(dealer, vulnerable, BidSequence::empty())
+	}
+
+	// the auction about to start on the deal
+	pub fn for_deal(deal: &Deal) -> Self {
+		Self::new(deal.dealer, deal.vulnerable)
+	}
+
+	pub fn from_bids(dealer: Seat, vulnerable: Vulnerability, bids: BidSequence) -> Self {
+		Auction {
+			dealer,
+			vulnerable,
+			bids,
+		}
+	}
+
+	// the calls as written in `BidSequence::parse`, the first made by the
+	// dealer
+	pub fn parse(s: &str, dealer: Seat, vulnerable: Vulnerability) -> Result<Self> {
+		Ok(Self::from_bids(dealer, vulnerable, BidSequence::parse(s)?))
+	}
+
+	pub fn dealer(&self) -> Seat {
+		self.dealer
+	}
+
+	pub fn vulnerable(&self) -> Vulnerability {
+		self.vulnerable
+	}
+
+	pub fn is_vulnerable(&self, seat: Seat) -> bool {
+		self.vulnerable.is_vulnerable(seat.side())
+	}
+
+	pub fn bids(&self) -> &BidSequence {
+		&self.bids
+	}
+
+	pub fn len(&self) -> usize {
+		self.bids.bids().len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.bids.bids().is_empty()
+	}
+
+	// the seat whose turn it is to call
+	pub fn next_seat(&self) -> Seat {
+		self.dealer.after(self.len())
+	}
+
+	// the seat that made the call, counting from the dealer's first call at 0
+	pub fn seat_of(&self, call: usize) -> Option<Seat> {
+		match call < self.len() {
+			true => Some(self.dealer.after(call)),
+			false => None,
+		}
+	}
+
+	pub fn call(&self, call: usize) -> Option<Call<'_>> {
+		Some(Call {
+			seat: self.seat_of(call)?,
+			bid: self.bids.bids()[call],
+			annotation: self.bids.annotation(call)?,
+		})
+	}
+
+	// every call in order, each with the seat that made it
+	pub fn calls(&self) -> impl Iterator<Item = Call<'_>> + '_ {
+		(0..self.len()).filter_map(move |call| self.call(call))
+	}
+
+	pub fn calls_by(&self, seat: Seat) -> impl Iterator<Item = Call<'_>> + '_ {
+		self.calls().filter(move |call| call.seat == seat)
+	}
+
+	pub fn calls_by_side(&self, side: Side) -> impl Iterator<Item = Call<'_>> + '_ {
+		self.calls().filter(move |call| call.seat.side() == side)
+	}
+
+	// the seat that made the first bid other than a pass, double or redouble
+	pub fn opener(&self) -> Option<Seat> {
+		self.calls()
+			.find(|call| matches!(call.bid, Bid::Contract(_)))
+			.map(|call| call.seat)
+	}
+
+	pub fn annotation(&self, call: usize) -> Option<&Annotation> {
+		self.bids.annotation(call)
+	}
+
+	pub fn annotate(&mut self, call: usize, annotation: Annotation) -> Result<()> {
+		self.bids.annotate(call, annotation)
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.bids.is_finished()
+	}
+
+	pub fn final_contract(&self) -> Result<AuctionResult> {
+		self.bids.final_contract(self.dealer)
+	}
+
+	pub fn valid_continuation(&self, next: Bid) -> bool {
+		self.bids.valid_continuation(next)
+	}
+
+	pub fn with_continuation(&self, next: Bid) -> Result<Auction> {
+		self.with_annotated_continuation(next, Annotation::default())
+	}
+
+	pub fn with_annotated_continuation(
+		&self,
+		next: Bid,
+		annotation: Annotation,
+	) -> Result<Auction> {
+		let bids = self.bids.with_annotated_continuation(next, annotation)?;
+		Ok(Self::from_bids(self.dealer, self.vulnerable, bids))
+	}
+}
+
+// the table of calls from north clockwise, with the dealer's first call in
+// its own column
+impl fmt::Display for Auction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.bids.fmt_table(f, self.dealer)
+	}
+}
@@ -0,0 +1,117 @@
+use super::Auction;
+use crate::{
+	Annotation, AuctionResult, Bid, Deal, Seat,
+	Seat::{East, North, South, West},
+	Side, Vulnerability,
+};
+
+fn auction(calls: &str, dealer: Seat) -> Auction {
+	Auction::parse(calls, dealer, Vulnerability::NS).unwrap()
+}
+
+#[test]
+fn seats_around_the_table() {
+	assert_eq!(North.lho(), East);
+	assert_eq!(North.partner(), South);
+	assert_eq!(North.rho(), West);
+	assert_eq!(West.lho(), North);
+	assert_eq!(East.rho(), North);
+	for seat in [North, East, South, West] {
+		assert_eq!(seat.lho().rho(), seat);
+		assert_eq!(seat.partner().side(), seat.side());
+		assert_eq!(seat.lho().side(), seat.side().opponents());
+		assert!(seat.side().seats().contains(&seat));
+	}
+	assert_eq!(South.side(), Side::NS);
+	assert_eq!(West.side(), Side::EW);
+}
+
+#[test]
+fn vulnerability_by_side() {
+	assert!(Vulnerability::NS.is_vulnerable(Side::NS));
+	assert!(!Vulnerability::NS.is_vulnerable(Side::EW));
+	assert!(Vulnerability::Both.is_vulnerable(Side::EW));
+	assert!(!Vulnerability::Neither.is_vulnerable(Side::NS));
+
+	let auction = auction("", West);
+	assert!(auction.is_vulnerable(South));
+	assert!(!auction.is_vulnerable(West));
+}
+
+#[test]
+fn calls_start_with_the_dealer() {
+	let auction = auction("Pass,1H,Dbl,2H", West);
+	let seats: Vec<Seat> = auction.calls().map(|call| call.seat).collect();
+	assert_eq!(seats, vec![West, North, East, South]);
+	assert_eq!(auction.seat_of(1), Some(North));
+	assert_eq!(auction.seat_of(4), None);
+	assert_eq!(auction.next_seat(), West);
+	assert_eq!(auction.opener(), Some(North));
+
+	let by_east: Vec<Bid> = auction.calls_by(East).map(|call| call.bid).collect();
+	assert_eq!(by_east, vec![Bid::Double]);
+	let by_ns: Vec<Bid> = auction
+		.calls_by_side(Side::NS)
+		.map(|call| call.bid)
+		.collect();
+	assert_eq!(
+		by_ns,
+		vec![Bid::parse("1H").unwrap(), Bid::parse("2H").unwrap()]
+	);
+}
+
+#[test]
+fn passed_hands_open_nothing() {
+	let auction = auction("Pass,Pass", South);
+	assert_eq!(auction.opener(), None);
+	assert_eq!(auction.next_seat(), North);
+	assert!(Auction::new(East, Vulnerability::Neither).is_empty());
+}
+
+#[test]
+fn declarer_follows_the_dealer() {
+	let calls = "1C,Pass,1S,Pass,2S,Pass,4S,Pass,Pass,Pass";
+	for (dealer, declarer) in [(North, South), (East, West), (South, North)] {
+		match auction(calls, dealer).final_contract().unwrap() {
+			AuctionResult::Contract(c) => assert_eq!(c.declarer, declarer),
+			AuctionResult::PassedOut => panic!("the auction was not passed out"),
+		}
+	}
+}
+
+#[test]
+fn continuing_keeps_the_dealer() {
+	let deal = Deal::from_seed(1);
+	let auction = Auction::for_deal(&deal)
+		.with_continuation(Bid::Pass)
+		.unwrap()
+		.with_annotated_continuation(Bid::parse("1NT").unwrap(), Annotation::alert())
+		.unwrap();
+	assert_eq!(auction.dealer(), deal.dealer);
+	assert_eq!(auction.vulnerable(), deal.vulnerable);
+	assert_eq!(auction.next_seat(), deal.dealer.after(2));
+	assert!(auction.call(1).unwrap().annotation.alert);
+	assert!(auction.with_continuation(Bid::Redouble).is_err());
+}
+
+#[test]
+fn table_puts_calls_under_their_seats() {
+	let mut auction = auction("1NT,Pass,2C!", East);
+	auction
+		.annotate(2, Annotation::explained(true, "Stayman"))
+		.unwrap();
+	let table = auction.to_string();
+	assert!(table.contains("|     | 1NT | Pass| 2C! |\n"));
+	assert!(table.ends_with("West 2C!: Stayman\n"));
+}
+
+#[test]
+fn serializes_with_the_dealer() {
+	let auction = auction("1S,Pass", East);
+	let json = serde_json::to_string(&auction).unwrap();
+	assert_eq!(
+		json,
+		r#"{"dealer":"East","vulnerable":"NS","bids":["1S","Pass"]}"#
+	);
+	assert_eq!(serde_json::from_str::<Auction>(&json).unwrap(), auction);
+}
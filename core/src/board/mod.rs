@@ -0,0 +1,123 @@
+//! Board numbers and the standard duplicate rotation of dealer and
+//! vulnerability, which repeats every sixteen boards.
+
+use super::{seeded_rng, Deal, Error, Result, Seat, Vulnerability};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, fmt};
+
+#[cfg(test)]
+mod tests;
+
+// the vulnerability of boards 1 to 16
+const VULNERABILITY: [Vulnerability; 16] = [
+	Vulnerability::Neither,
+	Vulnerability::NS,
+	Vulnerability::EW,
+	Vulnerability::Both,
+	Vulnerability::NS,
+	Vulnerability::EW,
+	Vulnerability::Both,
+	Vulnerability::Neither,
+	Vulnerability::EW,
+	Vulnerability::Both,
+	Vulnerability::Neither,
+	Vulnerability::NS,
+	Vulnerability::Both,
+	Vulnerability::Neither,
+	Vulnerability::NS,
+	Vulnerability::EW,
+];
+
+// deserialized through `Board::new`, so a stored board 0 is refused rather
+// than read as board 16
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u32")]
+pub struct Board(u32);
+
+impl TryFrom<u32> for Board {
+	type Error = Error;
+
+	fn try_from(number: u32) -> Result<Self> {
+		Board::new(number)
+	}
+}
+
+impl Board {
+	pub fn new(number: u32) -> Result<Self> {
+		if number == 0 {
+			return Err(Error::bridge("board numbers start at 1"));
+		}
+		Ok(Board(number))
+	}
+
+	// the first board of the rotation with this dealer and vulnerability
+	pub fn first_with(dealer: Seat, vulnerable: Vulnerability) -> Self {
+		let number = (1..=16)
+			.find(|n| {
+				let board = Board(*n);
+				board.dealer() == dealer && board.vulnerability() == vulnerable
+			})
+			.expect("every dealer and vulnerability appears in sixteen boards");
+		Board(number)
+	}
+
+	pub fn number(self) -> u32 {
+		self.0
+	}
+
+	// north deals board 1, east board 2 and so on around the table
+	pub fn dealer(self) -> Seat {
+		Seat::vec()[(self.0 as usize - 1) % 4]
+	}
+
+	pub fn vulnerability(self) -> Vulnerability {
+		VULNERABILITY[(self.0 as usize - 1) % 16]
+	}
+
+	pub fn deal(self) -> Deal {
+		self.deal_with_rng(&mut rand::thread_rng())
+	}
+
+	pub fn deal_with_rng<R: Rng + ?Sized>(self, rng: &mut R) -> Deal {
+		let deal = Deal::random_with_rng(rng);
+		Deal {
+			dealer: self.dealer(),
+			vulnerable: self.vulnerability(),
+			..deal
+		}
+	}
+
+	pub fn deal_from_seed(self, seed: u64) -> Deal {
+		self.deal_with_rng(&mut seeded_rng(seed))
+	}
+}
+
+impl fmt::Display for Board {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+// a set of boards numbered from 1, as played in a session or printed as
+// hand records
+pub fn deal_set(boards: u32) -> Vec<(Board, Deal)> {
+	deal_set_with_rng(boards, &mut rand::thread_rng())
+}
+
+pub fn deal_set_with_rng<R: Rng + ?Sized>(boards: u32, rng: &mut R) -> Vec<(Board, Deal)> {
+	(1..=boards)
+		.map(|n| (Board(n), Board(n).deal_with_rng(rng)))
+		.collect()
+}
+
+pub fn deal_set_from_seed(boards: u32, seed: u64) -> Vec<(Board, Deal)> {
+	deal_set_with_rng(boards, &mut seeded_rng(seed))
+}
+
+impl Deal {
+	// the first board number whose dealer and vulnerability match the deal's
+	pub fn board(&self) -> Board {
+		Board::first_with(self.dealer, self.vulnerable)
+	}
+}
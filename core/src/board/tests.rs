@@ -0,0 +1,87 @@
+use super::{deal_set, deal_set_from_seed, Board};
+use crate::{Seat, Vulnerability};
+
+macro_rules! test_rotation {
+    ($($name:ident: $value:expr,)*) => {
+        mod rotation {
+            use super::super::Board;
+            use crate::{Seat::*, Vulnerability::*};
+        $(
+            #[test]
+            fn $name() {
+                let (number, dealer, vulnerable) = $value;
+                let board = Board::new(number).unwrap();
+                assert_eq!(board.dealer(), dealer);
+                assert_eq!(board.vulnerability(), vulnerable);
+            }
+        )*
+        }
+    }
+}
+
+test_rotation! {
+	board_1: (1, North, Neither),
+	board_2: (2, East, NS),
+	board_3: (3, South, EW),
+	board_4: (4, West, Both),
+	board_5: (5, North, NS),
+	board_6: (6, East, EW),
+	board_7: (7, South, Both),
+	board_8: (8, West, Neither),
+	board_9: (9, North, EW),
+	board_10: (10, East, Both),
+	board_11: (11, South, Neither),
+	board_12: (12, West, NS),
+	board_13: (13, North, Both),
+	board_14: (14, East, Neither),
+	board_15: (15, South, NS),
+	board_16: (16, West, EW),
+	board_17: (17, North, Neither),
+	board_24: (24, West, Neither),
+	board_36: (36, West, Both),
+}
+
+#[test]
+fn rejects_board_zero() {
+	assert!(Board::new(0).is_err());
+}
+
+#[test]
+fn deserializing_checks_the_number() {
+	let board: Board = serde_json::from_str("7").unwrap();
+	assert_eq!(board, Board::new(7).unwrap());
+	assert_eq!(serde_json::to_string(&board).unwrap(), "7");
+	assert!(serde_json::from_str::<Board>("0").is_err());
+}
+
+#[test]
+fn first_with_matches_the_rotation() {
+	for number in 1..=16 {
+		let board = Board::new(number).unwrap();
+		assert_eq!(
+			Board::first_with(board.dealer(), board.vulnerability()),
+			board
+		);
+	}
+	let board = Board::first_with(Seat::East, Vulnerability::Neither);
+	assert_eq!(board.number(), 14);
+}
+
+#[test]
+fn deal_sets_follow_the_rotation() {
+	let set = deal_set(36);
+	assert_eq!(set.len(), 36);
+	for (idx, (board, deal)) in set.iter().enumerate() {
+		assert_eq!(board.number(), idx as u32 + 1);
+		assert_eq!(deal.dealer, board.dealer());
+		assert_eq!(deal.vulnerable, board.vulnerability());
+	}
+	assert_eq!(set[20].1.board().number(), 5);
+}
+
+#[test]
+fn seeded_deal_sets_are_reproducible() {
+	assert_eq!(deal_set_from_seed(8, 99), deal_set_from_seed(8, 99));
+	let board = Board::new(3).unwrap();
+	assert_eq!(board.deal_from_seed(5), board.deal_from_seed(5));
+}
@@ -0,0 +1,269 @@
+//! Putting a deal together from what is known about its hands. `build` takes
+//! four complete hands and checks that every seat has 13 cards and that no
+//! card is dealt twice, so a `Deal` from here or from one of the parsers
+//! always holds the whole deck.
+//!
+//! Any seat can instead be given only some of its cards, or the exact holding
+//! in a suit with `x` for small cards, and `deal` shares out the rest at
+//! random. Every deal with the cards asked for is equally likely; a
+//! constraint narrows that to the deals it matches.
+
+use super::{
+	cardset::CardSet,
+	constraint::{Constraint, MAX_ATTEMPTS},
+	notation, seeded_rng, Card, Deal, DealError, Error, Hand, Result, Seat, Suit, Vulnerability,
+};
+use rand::{seq::SliceRandom, Rng};
+
+#[cfg(test)]
+mod tests;
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+// the two to the nine of each suit, which an `x` stands for
+const SPOTS: u64 = 0xff | 0xff << 13 | 0xff << 26 | 0xff << 39;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DealBuilder {
+	dealer: Seat,
+	vulnerable: Vulnerability,
+	// the cards given to north, east, south and west
+	cards: [CardSet; 4],
+	// for each seat and suit given as an exact holding, the number of small
+	// cards in it still to be dealt
+	spots: [[Option<usize>; 4]; 4],
+	constraint: Option<Constraint>,
+	max_attempts: usize,
+}
+
+impl DealBuilder {
+	// north deals with no one vulnerable unless told otherwise
+	pub fn new() -> Self {
+		DealBuilder {
+			dealer: Seat::North,
+			vulnerable: Vulnerability::Neither,
+			cards: [CardSet::empty(); 4],
+			spots: [[None; 4]; 4],
+			constraint: None,
+			max_attempts: MAX_ATTEMPTS,
+		}
+	}
+
+	pub fn with_dealer(self, dealer: Seat) -> Self {
+		DealBuilder { dealer, ..self }
+	}
+
+	pub fn with_vulnerability(self, vulnerable: Vulnerability) -> Self {
+		DealBuilder { vulnerable, ..self }
+	}
+
+	// gives the seat its hand, replacing anything given before
+	pub fn with_hand(mut self, seat: Seat, hand: Hand) -> Self {
+		self.cards[seat.index()] = hand.cards();
+		self.spots[seat.index()] = [None; 4];
+		self
+	}
+
+	// gives the seat one more card, leaving the rest of its hand to chance
+	pub fn with_card(mut self, seat: Seat, card: Card) -> Self {
+		self.cards[seat.index()].insert(card);
+		self
+	}
+
+	// gives the seat exactly these cards in the suit, such as `AKQxx`, where
+	// an `x` is any card from the nine down that no one else was given
+	pub fn with_holding(mut self, seat: Seat, suit: Suit, holding: &str) -> Result<Self> {
+		let spots = holding
+			.chars()
+			.filter(|c| c.eq_ignore_ascii_case(&'x'))
+			.count();
+		let ranks: String = holding
+			.chars()
+			.filter(|c| !c.eq_ignore_ascii_case(&'x'))
+			.collect();
+		let mut cards = CardSet::empty();
+		for card in notation::english().parse_holding(suit, &ranks)? {
+			if !cards.insert(card) {
+				return Err(DealError::DuplicateCard(card).into());
+			}
+		}
+		let given = &mut self.cards[seat.index()];
+		*given = (*given - given.suit(suit)) | cards;
+		self.spots[seat.index()][suit as usize] = Some(spots);
+		Ok(self)
+	}
+
+	// only deals matching the constraint are dealt. It is read with the
+	// builder's dealer.
+	pub fn with_constraint(self, constraint: Constraint) -> Self {
+		DealBuilder {
+			constraint: Some(constraint),
+			..self
+		}
+	}
+
+	// the number of deals tried before giving up on the constraint, a million
+	// by default
+	pub fn with_max_attempts(self, max_attempts: usize) -> Self {
+		DealBuilder {
+			max_attempts,
+			..self
+		}
+	}
+
+	// the deal of the four hands given, which must be complete
+	pub fn build(self) -> Result<Deal> {
+		for seat in Seat::vec() {
+			let i = seat.index();
+			if self.cards[i].is_empty() && self.spots[i].iter().all(Option::is_none) {
+				return Err(DealError::MissingHand(seat).into());
+			}
+		}
+		let deal = self.deal_from(self.cards);
+		deal.check()?;
+		if let Some(constraint) = &self.constraint {
+			if !constraint.matches(&deal) {
+				return Err(Error::bridge(format!(
+					"the deal does not match '{}'",
+					constraint
+				)));
+			}
+		}
+		Ok(deal)
+	}
+
+	pub fn deal(&self) -> Result<Deal> {
+		self.deal_with_rng(&mut rand::thread_rng())
+	}
+
+	pub fn deal_from_seed(&self, seed: u64) -> Result<Deal> {
+		self.deal_with_rng(&mut seeded_rng(seed))
+	}
+
+	// deals the cards no one was given until the holdings and the constraint
+	// hold
+	pub fn deal_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Deal> {
+		let given = self.check()?;
+		let mut attempts = 0;
+		while attempts < self.max_attempts {
+			attempts += 1;
+			let mut hands = self.cards;
+			let mut free = given.complement();
+			for seat in Seat::vec() {
+				for suit in SUITS {
+					let n = self.spots[seat.index()][suit as usize].unwrap_or(0);
+					let pool: Vec<Card> = (free.suit(suit) & CardSet::from_bits(SPOTS))
+						.iter()
+						.collect();
+					for card in pool.choose_multiple(rng, n) {
+						hands[seat.index()].insert(*card);
+						free.remove(*card);
+					}
+				}
+			}
+			let mut rest: Vec<Card> = free.iter().collect();
+			rest.shuffle(rng);
+			let mut rest = rest.into_iter();
+			for seat in Seat::vec() {
+				let hand = &mut hands[seat.index()];
+				*hand |= rest.by_ref().take(13 - hand.len()).collect();
+			}
+			if !self.holdings_kept(&hands) {
+				continue;
+			}
+			let deal = self.deal_from(hands);
+			if self.constraint.as_ref().map_or(true, |c| c.matches(&deal)) {
+				return Ok(deal);
+			}
+		}
+		Err(match &self.constraint {
+			Some(constraint) => Error::bridge(format!(
+				"no deal matching '{}' found in {} attempts",
+				constraint, attempts
+			)),
+			None => Error::bridge(format!(
+				"no deal with the holdings given found in {} attempts",
+				attempts
+			)),
+		})
+	}
+
+	// the cards given to any seat, once it is sure that some deal holds them
+	fn check(&self) -> Result<CardSet> {
+		let mut given = CardSet::empty();
+		for seat in Seat::vec() {
+			let cards = self.cards[seat.index()];
+			if let Some(card) = (given & cards).iter().next() {
+				return Err(DealError::DuplicateCard(card).into());
+			}
+			given |= cards;
+			let len = cards.len() + self.spots(seat);
+			if len > 13 {
+				return Err(DealError::TooManyCards(len).into());
+			}
+		}
+
+		// the small cards left in each suit must cover its x's, and what is
+		// left after them must fit the seats that may still hold the suit
+		let mut left = [0; 4];
+		for suit in SUITS {
+			let spots: usize = Seat::vec()
+				.into_iter()
+				.map(|seat| self.spots[seat.index()][suit as usize].unwrap_or(0))
+				.sum();
+			let pool = (given.complement() & CardSet::from_bits(SPOTS)).suit_length(suit);
+			if spots > pool {
+				return Err(DealError::Impossible.into());
+			}
+			left[suit as usize] = 13 - given.suit_length(suit) - spots;
+		}
+		for subset in 1..16usize {
+			let in_subset = |suit: usize| subset & (1 << suit) != 0;
+			let cards: usize = (0..4).filter(|s| in_subset(*s)).map(|s| left[s]).sum();
+			let room: usize = Seat::vec()
+				.into_iter()
+				.filter(|seat| {
+					(0..4).any(|s| in_subset(s) && self.spots[seat.index()][s].is_none())
+				})
+				.map(|seat| 13 - self.cards[seat.index()].len() - self.spots(seat))
+				.sum();
+			if cards > room {
+				return Err(DealError::Impossible.into());
+			}
+		}
+		Ok(given)
+	}
+
+	fn spots(&self, seat: Seat) -> usize {
+		self.spots[seat.index()].iter().flatten().sum()
+	}
+
+	// whether every seat holds no more in each exact holding than it was given
+	fn holdings_kept(&self, hands: &[CardSet; 4]) -> bool {
+		Seat::vec().into_iter().all(|seat| {
+			SUITS.iter().all(|suit| {
+				self.spots[seat.index()][*suit as usize].map_or(true, |spots| {
+					let given = self.cards[seat.index()].suit_length(*suit);
+					hands[seat.index()].suit_length(*suit) == given + spots
+				})
+			})
+		})
+	}
+
+	fn deal_from(&self, hands: [CardSet; 4]) -> Deal {
+		Deal {
+			dealer: self.dealer,
+			vulnerable: self.vulnerable,
+			north: Hand::from_cards(hands[0]),
+			east: Hand::from_cards(hands[1]),
+			south: Hand::from_cards(hands[2]),
+			west: Hand::from_cards(hands[3]),
+		}
+	}
+}
+
+impl Default for DealBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
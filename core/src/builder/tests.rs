@@ -0,0 +1,173 @@
+use crate::{
+	cardset::CardSet,
+	constraint::{Constraint, MAX_ATTEMPTS},
+	Card, Deal, DealError, Error, Hand, Rank, Seat, Suit, Vulnerability,
+};
+
+fn hand(s: &str) -> Hand {
+	Hand::parse_partial(s).unwrap()
+}
+
+fn error(result: crate::Result<Deal>) -> DealError {
+	match result {
+		Err(Error::Deal(e)) => e,
+		other => panic!("expected a deal error, got {:?}", other),
+	}
+}
+
+fn builder() -> super::DealBuilder {
+	Deal::builder()
+		.with_hand(Seat::North, hand("AKQ|JT9|876|5432"))
+		.with_hand(Seat::East, hand("JT98|AKQ|5432|76"))
+		.with_hand(Seat::South, hand("765|8765|AKQ|JT9"))
+		.with_hand(Seat::West, hand("432|432|JT9|AKQ8"))
+}
+
+#[test]
+fn builds_a_full_deal() {
+	let deal = builder()
+		.with_dealer(Seat::West)
+		.with_vulnerability(Vulnerability::EW)
+		.build()
+		.unwrap();
+	assert_eq!(deal.dealer, Seat::West);
+	assert_eq!(deal.vulnerable, Vulnerability::EW);
+	assert_eq!(deal.hand_for_seat(Seat::East), &hand("JT98|AKQ|5432|76"));
+	assert_eq!(
+		deal,
+		Deal::parse_pbn(&deal.to_pbn(), Seat::West, Vulnerability::EW).unwrap()
+	);
+}
+
+#[test]
+fn defaults_to_north_dealing_with_no_one_vulnerable() {
+	let deal = builder().build().unwrap();
+	assert_eq!(deal.dealer, Seat::North);
+	assert_eq!(deal.vulnerable, Vulnerability::Neither);
+}
+
+#[test]
+fn rejects_a_missing_hand() {
+	let builder = Deal::builder().with_hand(Seat::North, hand("AKQ|JT9|876|5432"));
+	assert_eq!(error(builder.build()), DealError::MissingHand(Seat::East));
+}
+
+#[test]
+fn rejects_a_short_hand() {
+	let builder = builder().with_hand(Seat::South, hand("765|8765|AKQ|JT"));
+	assert_eq!(error(builder.build()), DealError::HandSize(Seat::South, 12));
+}
+
+#[test]
+fn rejects_a_card_dealt_twice() {
+	let builder = builder().with_hand(Seat::West, hand("432|432|JT9|AKQ9"));
+	let nine = Card {
+		rank: Rank::Nine,
+		suit: Suit::Clubs,
+	};
+	assert_eq!(error(builder.build()), DealError::DuplicateCard(nine));
+}
+
+fn south_with_spades() -> super::DealBuilder {
+	let heart_ace = Card {
+		rank: Rank::Ace,
+		suit: Suit::Hearts,
+	};
+	Deal::builder()
+		.with_holding(Seat::South, Suit::Spades, "AKQxx")
+		.unwrap()
+		.with_card(Seat::North, heart_ace)
+}
+
+#[test]
+fn deals_the_rest_around_a_known_hand() {
+	let south = hand("AKQ2|KJ4|Q93|K32");
+	let builder = Deal::builder().with_hand(Seat::South, south.clone());
+	for seed in 0..10 {
+		let deal = builder.deal_from_seed(seed).unwrap();
+		assert!(deal.check().is_ok());
+		assert_eq!(deal.south, south);
+	}
+	assert_eq!(
+		builder.deal_from_seed(3).unwrap(),
+		builder.deal_from_seed(3).unwrap()
+	);
+}
+
+#[test]
+fn keeps_exact_holdings_and_single_cards() {
+	let builder = south_with_spades();
+	let mut spots = CardSet::empty();
+	for seed in 0..200 {
+		let deal = builder.deal_from_seed(seed).unwrap();
+		assert!(deal.check().is_ok());
+		let spades = deal.south.cards().suit(Suit::Spades);
+		assert_eq!(spades.len(), 5);
+		assert_eq!(
+			spades.iter().take(3).collect::<Vec<_>>(),
+			hand("AKQ|||").cards().iter().collect::<Vec<_>>()
+		);
+		assert!(spades.iter().skip(3).all(|c| c.rank <= Rank::Nine));
+		spots |= spades;
+		assert!(deal.north.contains(Card {
+			rank: Rank::Ace,
+			suit: Suit::Hearts
+		}));
+	}
+	// every small spade turns up in south's hand at some point
+	assert_eq!(spots.len(), 11);
+}
+
+#[test]
+fn deals_the_free_seats_to_a_constraint() {
+	let constraint = Constraint::parse("hcp(north) >= 15 && hearts(east) >= 5").unwrap();
+	let builder = south_with_spades().with_constraint(constraint.clone());
+	for seed in 0..5 {
+		let deal = builder.deal_from_seed(seed).unwrap();
+		assert!(constraint.matches(&deal));
+		assert_eq!(deal.south.suit_holding(Suit::Spades).len(), 5);
+	}
+	let impossible = Constraint::parse("hcp(south) >= 30").unwrap();
+	assert!(south_with_spades()
+		.with_constraint(impossible)
+		.with_max_attempts(10)
+		.deal_from_seed(1)
+		.is_err());
+	assert_eq!(super::DealBuilder::new().max_attempts, MAX_ATTEMPTS);
+}
+
+#[test]
+fn rejects_cards_that_cannot_be_dealt() {
+	let twice = south_with_spades().with_hand(Seat::West, hand("A|||"));
+	let spade_ace = Card {
+		rank: Rank::Ace,
+		suit: Suit::Spades,
+	};
+	assert_eq!(error(twice.deal()), DealError::DuplicateCard(spade_ace));
+
+	let long = south_with_spades()
+		.with_holding(Seat::South, Suit::Hearts, "KQJT98xx")
+		.unwrap()
+		.with_card(
+			Seat::South,
+			Card {
+				rank: Rank::Ace,
+				suit: Suit::Clubs,
+			},
+		);
+	assert_eq!(error(long.deal()), DealError::TooManyCards(14));
+
+	let spots = Deal::builder()
+		.with_holding(Seat::North, Suit::Clubs, "xxxxx")
+		.unwrap()
+		.with_holding(Seat::South, Suit::Clubs, "xxxx")
+		.unwrap();
+	assert_eq!(error(spots.deal()), DealError::Impossible);
+
+	// with the other three void, west would need all thirteen spades
+	let mut crowded = Deal::builder().with_hand(Seat::West, hand("|A||"));
+	for seat in [Seat::North, Seat::East, Seat::South] {
+		crowded = crowded.with_holding(seat, Suit::Spades, "").unwrap();
+	}
+	assert_eq!(error(crowded.deal()), DealError::Impossible);
+}
@@ -0,0 +1,251 @@
+//! Sets of cards as the bits of a `u64`, thirteen bits to a suit from clubs
+//! up to spades with the two lowest in each. Hands are built on them, so
+//! lengths, point counts and set operations take a few instructions whatever
+//! the size of the set.
+
+use super::{Card, Rank, Suit};
+use std::{fmt, iter::FromIterator, ops};
+
+#[cfg(test)]
+mod tests;
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+const RANKS: [Rank; 13] = [
+	Rank::Two,
+	Rank::Three,
+	Rank::Four,
+	Rank::Five,
+	Rank::Six,
+	Rank::Seven,
+	Rank::Eight,
+	Rank::Nine,
+	Rank::Ten,
+	Rank::Jack,
+	Rank::Queen,
+	Rank::King,
+	Rank::Ace,
+];
+
+const SUIT_MASK: u64 = (1 << 13) - 1;
+
+const ALL: u64 = (1 << 52) - 1;
+
+// the high card points of each holding in a suit
+const HCP: [u8; 1 << 13] = hcp_table();
+
+const fn hcp_table() -> [u8; 1 << 13] {
+	let mut table = [0; 1 << 13];
+	let mut holding = 0;
+	while holding < table.len() {
+		let ace = (holding >> 12) & 1;
+		let king = (holding >> 11) & 1;
+		let queen = (holding >> 10) & 1;
+		let jack = (holding >> 9) & 1;
+		table[holding] = (4 * ace + 3 * king + 2 * queen + jack) as u8;
+		holding += 1;
+	}
+	table
+}
+
+fn bit(card: Card) -> u64 {
+	1 << (card.suit as usize * 13 + card.rank as usize)
+}
+
+fn card(index: usize) -> Card {
+	Card {
+		rank: RANKS[index % 13],
+		suit: SUITS[index / 13],
+	}
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CardSet(u64);
+
+impl CardSet {
+	pub fn empty() -> Self {
+		CardSet(0)
+	}
+
+	// the whole deck
+	pub fn full() -> Self {
+		CardSet(ALL)
+	}
+
+	// the set with the given bits, ignoring any above the 52 cards
+	pub fn from_bits(bits: u64) -> Self {
+		CardSet(bits & ALL)
+	}
+
+	pub fn bits(self) -> u64 {
+		self.0
+	}
+
+	pub fn len(self) -> usize {
+		self.0.count_ones() as usize
+	}
+
+	pub fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	pub fn contains(self, card: Card) -> bool {
+		self.0 & bit(card) != 0
+	}
+
+	// adds the card, giving whether it was missing
+	pub fn insert(&mut self, card: Card) -> bool {
+		let missing = !self.contains(card);
+		self.0 |= bit(card);
+		missing
+	}
+
+	// takes the card out, giving whether it was there
+	pub fn remove(&mut self, card: Card) -> bool {
+		let present = self.contains(card);
+		self.0 &= !bit(card);
+		present
+	}
+
+	pub fn union(self, other: CardSet) -> Self {
+		CardSet(self.0 | other.0)
+	}
+
+	pub fn intersection(self, other: CardSet) -> Self {
+		CardSet(self.0 & other.0)
+	}
+
+	pub fn difference(self, other: CardSet) -> Self {
+		CardSet(self.0 & !other.0)
+	}
+
+	// the cards of the deck not in the set
+	pub fn complement(self) -> Self {
+		CardSet(!self.0 & ALL)
+	}
+
+	pub fn is_subset(self, other: CardSet) -> bool {
+		self.0 & !other.0 == 0
+	}
+
+	pub fn is_disjoint(self, other: CardSet) -> bool {
+		self.0 & other.0 == 0
+	}
+
+	// the cards of one suit, a bit for each rank with the two lowest
+	pub fn holding(self, suit: Suit) -> u16 {
+		((self.0 >> (suit as usize * 13)) & SUIT_MASK) as u16
+	}
+
+	pub fn suit(self, suit: Suit) -> Self {
+		CardSet(self.0 & (SUIT_MASK << (suit as usize * 13)))
+	}
+
+	pub fn suit_length(self, suit: Suit) -> usize {
+		self.holding(suit).count_ones() as usize
+	}
+
+	pub fn hcp(self) -> usize {
+		SUITS
+			.iter()
+			.map(|suit| HCP[self.holding(*suit) as usize] as usize)
+			.sum()
+	}
+
+	// the cards from the ace of spades down to the two of clubs
+	pub fn iter(self) -> Iter {
+		Iter(self.0)
+	}
+}
+
+impl fmt::Debug for CardSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_set()
+			.entries(self.iter().map(|card| card.to_string()))
+			.finish()
+	}
+}
+
+impl ops::BitOr for CardSet {
+	type Output = CardSet;
+
+	fn bitor(self, other: CardSet) -> CardSet {
+		self.union(other)
+	}
+}
+
+impl ops::BitAnd for CardSet {
+	type Output = CardSet;
+
+	fn bitand(self, other: CardSet) -> CardSet {
+		self.intersection(other)
+	}
+}
+
+impl ops::Sub for CardSet {
+	type Output = CardSet;
+
+	fn sub(self, other: CardSet) -> CardSet {
+		self.difference(other)
+	}
+}
+
+impl ops::BitOrAssign for CardSet {
+	fn bitor_assign(&mut self, other: CardSet) {
+		self.0 |= other.0;
+	}
+}
+
+impl ops::SubAssign for CardSet {
+	fn sub_assign(&mut self, other: CardSet) {
+		self.0 &= !other.0;
+	}
+}
+
+impl FromIterator<Card> for CardSet {
+	fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+		let mut set = CardSet::empty();
+		for card in cards {
+			set.insert(card);
+		}
+		set
+	}
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+	fn from_iter<I: IntoIterator<Item = &'a Card>>(cards: I) -> Self {
+		cards.into_iter().cloned().collect()
+	}
+}
+
+impl IntoIterator for CardSet {
+	type Item = Card;
+	type IntoIter = Iter;
+
+	fn into_iter(self) -> Iter {
+		self.iter()
+	}
+}
+
+// the cards of a set from the highest bit down
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+	type Item = Card;
+
+	fn next(&mut self) -> Option<Card> {
+		if self.0 == 0 {
+			return None;
+		}
+		let index = 63 - self.0.leading_zeros() as usize;
+		self.0 &= !(1 << index);
+		Some(card(index))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.0.count_ones() as usize;
+		(len, Some(len))
+	}
+}
+
+impl ExactSizeIterator for Iter {}
@@ -0,0 +1,101 @@
+use super::CardSet;
+use crate::{Card, Deck, Hand, Rank, Suit};
+use rand::Rng;
+
+fn card(s: &str) -> Card {
+	Card {
+		suit: Suit::parse(&s[..1]).unwrap(),
+		rank: Rank::parse(&s[1..]).unwrap(),
+	}
+}
+
+fn set(cards: &[&str]) -> CardSet {
+	cards.iter().map(|c| card(c)).collect()
+}
+
+macro_rules! test_hcp {
+	($name:ident, $hand:expr, $hcp:expr) => {
+		#[test]
+		fn $name() {
+			assert_eq!(Hand::parse_partial($hand).unwrap().cards().hcp(), $hcp);
+		}
+	};
+}
+
+test_hcp!(hcp_void, "|||", 0);
+test_hcp!(hcp_yarborough, "98765|432|98|765", 0);
+test_hcp!(hcp_honours, "AKQJ|AKQJ|AKQJ|A", 34);
+test_hcp!(hcp_mixed, "KJ4|Q93|A862|T75", 10);
+
+#[test]
+fn hcp_matches_counting_card_by_card() {
+	for seed in 0..20 {
+		let deck: Deck = crate::seeded_rng(seed).gen();
+		let cards: CardSet = deck.0[..13].iter().collect();
+		let expected: usize = cards.iter().map(|c| c.rank.hcp()).sum();
+		assert_eq!(cards.hcp(), expected);
+	}
+}
+
+#[test]
+fn inserts_and_removes() {
+	let mut cards = CardSet::empty();
+	assert!(cards.is_empty());
+	assert!(cards.insert(card("SA")));
+	assert!(!cards.insert(card("SA")));
+	assert!(cards.insert(card("C2")));
+	assert_eq!(cards.len(), 2);
+	assert!(cards.contains(card("SA")));
+	assert!(!cards.contains(card("HA")));
+	assert!(cards.remove(card("SA")));
+	assert!(!cards.remove(card("SA")));
+	assert_eq!(cards, set(&["C2"]));
+}
+
+#[test]
+fn set_operations() {
+	let a = set(&["SA", "SK", "H2"]);
+	let b = set(&["SK", "D3"]);
+	assert_eq!(a | b, set(&["SA", "SK", "H2", "D3"]));
+	assert_eq!(a & b, set(&["SK"]));
+	assert_eq!(a - b, set(&["SA", "H2"]));
+	assert!(set(&["SK"]).is_subset(a));
+	assert!(!b.is_subset(a));
+	assert!((a - b).is_disjoint(b));
+	assert_eq!(a.complement().len(), 49);
+	assert_eq!(a.complement() | a, CardSet::full());
+	assert_eq!(CardSet::from_bits(u64::MAX), CardSet::full());
+}
+
+#[test]
+fn suit_lengths() {
+	let cards = Hand::parse("AKQJT|9|876|5432").unwrap().cards();
+	assert_eq!(cards.suit_length(Suit::Spades), 5);
+	assert_eq!(cards.suit_length(Suit::Hearts), 1);
+	assert_eq!(cards.suit_length(Suit::Diamonds), 3);
+	assert_eq!(cards.suit_length(Suit::Clubs), 4);
+	assert_eq!(cards.suit(Suit::Diamonds), set(&["D8", "D7", "D6"]));
+	assert_eq!(cards.holding(Suit::Hearts), 1 << Rank::Nine as usize);
+}
+
+#[test]
+fn iterates_from_the_top() {
+	let cards = set(&["C2", "HA", "SA", "H3"]);
+	let order: Vec<Card> = cards.iter().collect();
+	assert_eq!(order, vec![card("SA"), card("HA"), card("H3"), card("C2")]);
+	assert_eq!(cards.iter().len(), 4);
+	assert_eq!(CardSet::full().iter().count(), 52);
+}
+
+#[test]
+fn hands_serialize_as_lists_of_cards() {
+	let hand = Hand::parse_partial("A||K|2").unwrap();
+	let json = serde_json::to_string(&hand).unwrap();
+	assert_eq!(
+		json,
+		r#"[{"rank":"Ace","suit":"Spades"},{"rank":"King","suit":"Diamonds"},{"rank":"Two","suit":"Clubs"}]"#
+	);
+	let hand = Hand::parse("AKQ2|KJ4|Q93|K32").unwrap();
+	let json = serde_json::to_string(&hand).unwrap();
+	assert_eq!(serde_json::from_str::<Hand>(&json).unwrap(), hand);
+}
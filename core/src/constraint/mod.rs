@@ -0,0 +1,888 @@
+//! A small language for describing deals, in the style of Thomas Andrews'
+//! `dealer` program.
+//!
+//! A constraint is an expression over the four hands, such as
+//!
+//! ```text
+//! hcp(dealer) >= 15 && hcp(dealer) <= 17 && shape(dealer, any 4333 + any 4432 + any 5332)
+//! ```
+//!
+//! Seats are either absolute (`north`, `east`, `south`, `west`) or relative
+//! to the dealer (`dealer`/`first`, `second`, `third`, `fourth`). Values are
+//! integers, and comparisons and boolean operators produce 1 or 0.
+//!
+//! The functions available are `hcp(seat)` and `hcp(seat, suit)`,
+//! `controls(seat)` and `controls(seat, suit)`, the suit lengths `spades(seat)`,
+//! `hearts(seat)`, `diamonds(seat)` and `clubs(seat)`, `balanced(seat)`,
+//! `shape(seat, patterns)`, the evaluations `losers(seat)`, `zar(seat)` and
+//! `cccc(seat)` (in hundredths of a point), and `quality(seat, suit)` and
+//! `stopper(seat, suit)` for single suits. A shape pattern lists suit lengths in
+//! spade-heart-diamond-club order with `x` for any length, optionally prefixed
+//! by `any` to match every ordering; patterns are combined with `+` and
+//! excluded with `-`. This is `dealer`'s syntax, kept so its scripts carry
+//! over, but each pattern is matched as the `shape::DistPattern` it stands
+//! for, so `any 5xx4` means the same as `5-4-any-any`.
+
+use super::{
+	lengths::{self, Lengths, Row},
+	seeded_rng,
+	shape::DistPattern,
+	Card, Deal, Dist, Error, Hand, Result, Seat, Suit, Vulnerability,
+};
+use rand::Rng;
+use std::{fmt, sync::OnceLock};
+
+#[cfg(test)]
+mod tests;
+
+// the most high card points one hand can hold, and all four hands together
+const MAX_HCP: usize = 37;
+const TOTAL_HCP: usize = 40;
+
+// how many deals are tried for a constraint unless told otherwise
+pub(crate) const MAX_ATTEMPTS: usize = 1_000_000;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SeatRef {
+	Absolute(Seat),
+	// offset clockwise from the dealer
+	Relative(usize),
+}
+
+impl SeatRef {
+	fn parse(s: &str) -> Result<Self> {
+		use self::SeatRef::*;
+		match s {
+			"north" => Ok(Absolute(Seat::North)),
+			"east" => Ok(Absolute(Seat::East)),
+			"south" => Ok(Absolute(Seat::South)),
+			"west" => Ok(Absolute(Seat::West)),
+			"dealer" | "first" => Ok(Relative(0)),
+			"second" => Ok(Relative(1)),
+			"third" => Ok(Relative(2)),
+			"fourth" => Ok(Relative(3)),
+			_ => Err(Error::parse(format!("invalid seat '{}'", s))),
+		}
+	}
+
+	fn resolve(self, dealer: Seat) -> Seat {
+		match self {
+			SeatRef::Absolute(seat) => seat,
+			SeatRef::Relative(offset) => dealer.after(offset),
+		}
+	}
+}
+
+// one of `dealer`'s shape patterns, such as `any 4333` or `54xx`, read as
+// the `DistPattern` it stands for: `4-3-3-3` or `5=4=any=any`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapePattern(DistPattern);
+
+impl ShapePattern {
+	fn parse(any: bool, s: &str) -> Result<Self> {
+		let chars: Vec<char> = s.chars().collect();
+		if chars.len() != 4 {
+			return Err(Error::parse(format!(
+				"shape pattern '{}' must have four suits",
+				s
+			)));
+		}
+		let mut lengths = Vec::new();
+		for c in chars {
+			lengths.push(match c {
+				'x' => "any".to_string(),
+				c => c
+					.to_digit(10)
+					.ok_or_else(|| {
+						Error::parse(format!("invalid suit length '{}' in shape '{}'", c, s))
+					})?
+					.to_string(),
+			});
+		}
+		let pattern = match any {
+			true => lengths.join("-"),
+			false => lengths.join("="),
+		};
+		DistPattern::parse(&pattern)
+			.map(ShapePattern)
+			.map_err(|_| Error::parse(format!("shape '{}' is impossible", s)))
+	}
+
+	pub fn matches(&self, dist: &Dist) -> bool {
+		self.0.matches(dist)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+	include: Vec<ShapePattern>,
+	exclude: Vec<ShapePattern>,
+}
+
+impl Shape {
+	pub fn matches(&self, dist: &Dist) -> bool {
+		self.include.iter().any(|p| p.matches(dist))
+			&& !self.exclude.iter().any(|p| p.matches(dist))
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Op {
+	Or,
+	And,
+	Eq,
+	Ne,
+	Lt,
+	Le,
+	Gt,
+	Ge,
+	Add,
+	Sub,
+}
+
+impl Op {
+	fn apply(self, l: i64, r: i64) -> i64 {
+		match self {
+			Op::Or => (l != 0 || r != 0) as i64,
+			Op::And => (l != 0 && r != 0) as i64,
+			Op::Eq => (l == r) as i64,
+			Op::Ne => (l != r) as i64,
+			Op::Lt => (l < r) as i64,
+			Op::Le => (l <= r) as i64,
+			Op::Gt => (l > r) as i64,
+			Op::Ge => (l >= r) as i64,
+			Op::Add => l + r,
+			Op::Sub => l - r,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+	Num(i64),
+	Not(Box<Expr>),
+	Binary(Op, Box<Expr>, Box<Expr>),
+	Hcp(SeatRef, Option<Suit>),
+	Controls(SeatRef, Option<Suit>),
+	Length(SeatRef, Suit),
+	Balanced(SeatRef),
+	Shape(SeatRef, Shape),
+	Losers(SeatRef),
+	Zar(SeatRef),
+	Cccc(SeatRef),
+	Quality(SeatRef, Suit),
+	Stopper(SeatRef, Suit),
+}
+
+impl Expr {
+	fn eval(&self, deal: &Deal, dealer: Seat) -> i64 {
+		use self::Expr::*;
+		let hand = |seat: &SeatRef| deal.hand_for_seat(seat.resolve(dealer));
+		match self {
+			Num(n) => *n,
+			Not(e) => (e.eval(deal, dealer) == 0) as i64,
+			Binary(op, l, r) => {
+				let l = l.eval(deal, dealer);
+				// short-circuit the boolean operators
+				match op {
+					Op::Or if l != 0 => return 1,
+					Op::And if l == 0 => return 0,
+					_ => (),
+				}
+				op.apply(l, r.eval(deal, dealer))
+			}
+			Hcp(seat, suit) => sum_cards(hand(seat), *suit, |c| c.rank.hcp()),
+			Controls(seat, None) => hand(seat).controls() as i64,
+			Controls(seat, Some(suit)) => hand(seat).suit_controls(*suit) as i64,
+			Length(seat, suit) => hand(seat).suit_holding(*suit).len() as i64,
+			Balanced(seat) => hand(seat).distribution().is_balanced() as i64,
+			Shape(seat, shape) => shape.matches(&hand(seat).distribution()) as i64,
+			Losers(seat) => hand(seat).losers() as i64,
+			Zar(seat) => hand(seat).zar_points() as i64,
+			Cccc(seat) => hand(seat).cccc_hundredths(),
+			Quality(seat, suit) => hand(seat).suit_quality(*suit) as i64,
+			Stopper(seat, suit) => hand(seat).stopper(*suit) as i64,
+		}
+	}
+
+	// the value from the suit lengths of one seat alone, spades first, or none
+	// if it depends on anything more
+	fn eval_lengths(&self, seat: Seat, dealer: Seat, lengths: [usize; 4]) -> Option<i64> {
+		use self::Expr::*;
+		let mine = |s: &SeatRef| s.resolve(dealer) == seat;
+		let dist = Dist {
+			spades: lengths[0],
+			hearts: lengths[1],
+			diamonds: lengths[2],
+			clubs: lengths[3],
+		};
+		match self {
+			Num(n) => Some(*n),
+			Not(e) => Some((e.eval_lengths(seat, dealer, lengths)? == 0) as i64),
+			Binary(op, l, r) => {
+				let l = l.eval_lengths(seat, dealer, lengths)?;
+				Some(op.apply(l, r.eval_lengths(seat, dealer, lengths)?))
+			}
+			Length(s, suit) if mine(s) => Some(lengths[3 - *suit as usize] as i64),
+			Balanced(s) if mine(s) => Some(dist.is_balanced() as i64),
+			Shape(s, shape) if mine(s) => Some(shape.matches(&dist) as i64),
+			_ => None,
+		}
+	}
+
+	// the value from the high card points of one seat alone, or none if it
+	// depends on anything more
+	fn eval_hcp(&self, seat: Seat, dealer: Seat, hcp: i64) -> Option<i64> {
+		use self::Expr::*;
+		match self {
+			Num(n) => Some(*n),
+			Not(e) => Some((e.eval_hcp(seat, dealer, hcp)? == 0) as i64),
+			Binary(op, l, r) => {
+				let l = l.eval_hcp(seat, dealer, hcp)?;
+				Some(op.apply(l, r.eval_hcp(seat, dealer, hcp)?))
+			}
+			Hcp(s, None) if s.resolve(dealer) == seat => Some(hcp),
+			_ => None,
+		}
+	}
+
+	// for each seat, which of the rows it can have when the expression is
+	// true, or false if `want` is false. Only what follows from one seat's
+	// suit lengths is used, so every deal giving the expression that value
+	// has allowed rows, though not every deal with allowed rows does.
+	fn rows_allowed(&self, dealer: Seat, rows: &[Row], want: bool) -> [Vec<bool>; 4] {
+		self.allowed(rows.len(), want, &|expr: &Expr, seat, i| {
+			let row = rows[i];
+			expr.eval_lengths(seat, dealer, [row[3], row[2], row[1], row[0]])
+		})
+	}
+
+	// the same for each seat's high card points, from 0 to 37
+	fn hcp_allowed(&self, dealer: Seat, want: bool) -> [Vec<bool>; 4] {
+		self.allowed(MAX_HCP + 1, want, &|expr: &Expr, seat, hcp| {
+			expr.eval_hcp(seat, dealer, hcp as i64)
+		})
+	}
+
+	// for each seat, which of `options` values it can have when the
+	// expression is `want`, where `value` works out a subexpression from one
+	// seat's value alone, or gives none if it depends on more
+	fn allowed<F>(&self, options: usize, want: bool, value: &F) -> [Vec<bool>; 4]
+	where
+		F: Fn(&Expr, Seat, usize) -> Option<i64>,
+	{
+		use self::Expr::*;
+		let mut allowed = [(); 4].map(|_| vec![true; options]);
+		let mut exact = false;
+		for seat in Seat::vec() {
+			if value(self, seat, 0).is_none() {
+				continue;
+			}
+			exact = true;
+			allowed[seat.index()] = (0..options)
+				.map(|i| (value(self, seat, i) != Some(0)) == want)
+				.collect();
+		}
+		if exact {
+			return allowed;
+		}
+		let combine = |l: &Expr, r: &Expr, both: bool| {
+			let l = l.allowed(options, want, value);
+			let r = r.allowed(options, want, value);
+			let mut allowed = l;
+			for (seat, r) in allowed.iter_mut().zip(r) {
+				for (a, b) in seat.iter_mut().zip(r) {
+					*a = if both { *a && b } else { *a || b };
+				}
+			}
+			allowed
+		};
+		match (self, want) {
+			(Not(e), _) => e.allowed(options, !want, value),
+			(Binary(Op::And, l, r), true) | (Binary(Op::Or, l, r), false) => combine(l, r, true),
+			(Binary(Op::And, l, r), false) | (Binary(Op::Or, l, r), true) => combine(l, r, false),
+			_ => allowed,
+		}
+	}
+
+	fn is_relative(&self) -> bool {
+		use self::Expr::*;
+		match self {
+			Num(_) => false,
+			Not(e) => e.is_relative(),
+			Binary(_, l, r) => l.is_relative() || r.is_relative(),
+			Hcp(seat, _)
+			| Controls(seat, _)
+			| Length(seat, _)
+			| Balanced(seat)
+			| Shape(seat, _)
+			| Losers(seat)
+			| Zar(seat)
+			| Cccc(seat)
+			| Quality(seat, _)
+			| Stopper(seat, _) => {
+				matches!(seat, SeatRef::Relative(_))
+			}
+		}
+	}
+}
+
+fn sum_cards<F: Fn(Card) -> usize>(hand: &Hand, suit: Option<Suit>, value: F) -> i64 {
+	let cards = match suit {
+		Some(suit) => hand.cards().suit(suit),
+		None => hand.cards(),
+	};
+	cards.iter().map(value).sum::<usize>() as i64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+	expr: Expr,
+	text: String,
+}
+
+impl Constraint {
+	pub fn parse(s: &str) -> Result<Self> {
+		let tokens = tokenize(s)?;
+		let mut parser = Parser { tokens, pos: 0 };
+		let expr = parser.parse_or()?;
+		if let Some(token) = parser.peek() {
+			return Err(Error::parse(format!(
+				"unexpected '{}' in constraint",
+				token
+			)));
+		}
+		Ok(Constraint {
+			expr,
+			text: s.trim().to_string(),
+		})
+	}
+
+	pub fn expr(&self) -> &Expr {
+		&self.expr
+	}
+
+	// whether the constraint mentions seats relative to the dealer, in which
+	// case the generator chooses the dealer
+	pub fn is_relative(&self) -> bool {
+		self.expr.is_relative()
+	}
+
+	pub fn matches(&self, deal: &Deal) -> bool {
+		self.matches_with_dealer(deal, deal.dealer)
+	}
+
+	fn matches_with_dealer(&self, deal: &Deal, dealer: Seat) -> bool {
+		self.expr.eval(deal, dealer) != 0
+	}
+}
+
+impl fmt::Display for Constraint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.text)
+	}
+}
+
+// deals matching a constraint. The suit lengths of all four hands are chosen
+// first, as often as a random deal would give them, from those the constraint
+// allows, and the cards are then dealt to fit them, so rare shapes come as
+// quickly as common ones and every matching deal is equally likely.
+pub struct Generator {
+	constraint: Constraint,
+	max_attempts: usize,
+	// the suit lengths allowed with each dealer, and whether the high card
+	// points can be shared out as the constraint asks, worked out when first
+	// needed
+	lengths: [OnceLock<Lengths>; 4],
+	hcp_possible: [OnceLock<bool>; 4],
+}
+
+impl Generator {
+	pub fn new(constraint: Constraint) -> Self {
+		Generator {
+			constraint,
+			max_attempts: MAX_ATTEMPTS,
+			lengths: Default::default(),
+			hcp_possible: Default::default(),
+		}
+	}
+
+	// the number of deals tried before giving up, a million by default
+	pub fn with_max_attempts(self, max_attempts: usize) -> Self {
+		Generator {
+			max_attempts,
+			..self
+		}
+	}
+
+	// the share of random deals, with any dealer, whose suit lengths the
+	// constraint allows, which is zero when no deal can match
+	pub fn shape_probability(&self) -> f64 {
+		let total: f64 = Seat::vec()
+			.into_iter()
+			.map(|dealer| self.lengths(dealer).probability())
+			.sum();
+		total / 4.0
+	}
+
+	pub fn generate(&self) -> Result<Deal> {
+		self.generate_with_rng(&mut rand::thread_rng())
+	}
+
+	pub fn generate_from_seed(&self, seed: u64) -> Result<Deal> {
+		self.generate_with_rng(&mut seeded_rng(seed))
+	}
+
+	// a random deal matching the constraint, with a random dealer and
+	// vulnerability. A dealer-relative constraint is read with that dealer.
+	pub fn generate_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Deal> {
+		self.next(rng, &mut self.stats())
+	}
+
+	pub fn generate_many(&self, count: usize) -> Result<(Vec<Deal>, Stats)> {
+		self.generate_many_with_rng(count, &mut rand::thread_rng())
+	}
+
+	// the deals with how many were turned down on the way
+	pub fn generate_many_with_rng<R: Rng + ?Sized>(
+		&self,
+		count: usize,
+		rng: &mut R,
+	) -> Result<(Vec<Deal>, Stats)> {
+		let mut stats = self.stats();
+		let deals = (0..count)
+			.map(|_| self.next(rng, &mut stats))
+			.collect::<Result<_>>()?;
+		Ok((deals, stats))
+	}
+
+	fn stats(&self) -> Stats {
+		Stats {
+			shape_probability: self.shape_probability(),
+			dealt: 0,
+			matched: 0,
+		}
+	}
+
+	fn lengths(&self, dealer: Seat) -> &Lengths {
+		// every dealer allows the same lengths when no seat depends on it
+		let dealer = match self.constraint.is_relative() {
+			true => dealer,
+			false => Seat::North,
+		};
+		self.lengths[dealer.index()].get_or_init(|| {
+			let rows = lengths::rows();
+			let allowed = self.constraint.expr.rows_allowed(dealer, &rows, true);
+			Lengths::new(allowed.map(|allowed| {
+				rows.iter()
+					.zip(allowed)
+					.filter(|(_, allowed)| *allowed)
+					.map(|(row, _)| *row)
+					.collect()
+			}))
+		})
+	}
+
+	// whether some way of sharing out the 40 high card points gives every
+	// seat an amount the constraint allows
+	fn hcp_possible(&self, dealer: Seat) -> bool {
+		let dealer = match self.constraint.is_relative() {
+			true => dealer,
+			false => Seat::North,
+		};
+		*self.hcp_possible[dealer.index()].get_or_init(|| {
+			let mut reached = vec![false; TOTAL_HCP + 1];
+			reached[0] = true;
+			for allowed in self.constraint.expr.hcp_allowed(dealer, true) {
+				let mut next = vec![false; TOTAL_HCP + 1];
+				for sum in (0..=TOTAL_HCP).filter(|sum| reached[*sum]) {
+					for hcp in (0..=MAX_HCP).filter(|hcp| allowed[*hcp]) {
+						if sum + hcp <= TOTAL_HCP {
+							next[sum + hcp] = true;
+						}
+					}
+				}
+				reached = next;
+			}
+			reached[TOTAL_HCP]
+		})
+	}
+
+	// for each dealer, the share of random deals it deals whose suit lengths
+	// the constraint allows, or zero if the points cannot be shared out
+	fn dealer_weights(&self) -> Vec<(Seat, f64)> {
+		Seat::vec()
+			.into_iter()
+			.map(|dealer| match self.hcp_possible(dealer) {
+				true => (dealer, self.lengths(dealer).probability()),
+				false => (dealer, 0.0),
+			})
+			.filter(|(_, weight)| *weight > 0.0)
+			.collect()
+	}
+
+	fn next<R: Rng + ?Sized>(&self, rng: &mut R, stats: &mut Stats) -> Result<Deal> {
+		// each dealer comes up as often as it deals a deal that can match
+		let weights = self.dealer_weights();
+		if weights.is_empty() {
+			let what = match self.shape_probability() > 0.0 {
+				true => "high card points",
+				false => "suit lengths",
+			};
+			return Err(Error::bridge(format!(
+				"no deal has the {} '{}' asks for",
+				what, self.constraint
+			)));
+		}
+		let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+		let mut attempts = 0;
+		while attempts < self.max_attempts {
+			attempts += 1;
+			let mut pick = rng.gen::<f64>() * total;
+			let mut dealer = weights[weights.len() - 1].0;
+			for (seat, weight) in &weights {
+				if pick < *weight {
+					dealer = *seat;
+					break;
+				}
+				pick -= weight;
+			}
+			let vulnerable: Vulnerability = rng.gen();
+			let rows = match self.lengths(dealer).sample(rng) {
+				Some(rows) => rows,
+				None => continue,
+			};
+			let deal = lengths::deal(&rows, dealer, vulnerable, rng);
+			stats.dealt += 1;
+			if self.constraint.matches(&deal) {
+				stats.matched += 1;
+				return Ok(deal);
+			}
+		}
+		Err(Error::bridge(format!(
+			"no deal matching '{}' found in {} attempts",
+			self.constraint, attempts
+		)))
+	}
+}
+
+// how generating went: the share of random deals with suit lengths the
+// constraint allows, and how many deals were dealt to those lengths to find
+// the ones that matched
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stats {
+	pub shape_probability: f64,
+	pub dealt: usize,
+	pub matched: usize,
+}
+
+impl Stats {
+	// the share of deals with allowed suit lengths that the rest of the
+	// constraint turned down
+	pub fn rejection_rate(&self) -> f64 {
+		match self.dealt {
+			0 => 0.0,
+			dealt => 1.0 - self.matched as f64 / dealt as f64,
+		}
+	}
+
+	// an estimate of the share of all random deals that match
+	pub fn probability(&self) -> f64 {
+		self.shape_probability * (1.0 - self.rejection_rate())
+	}
+}
+
+impl fmt::Display for Stats {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} of {} deals matched ({:.1}% rejected), shapes fit {:.4}% of deals",
+			self.matched,
+			self.dealt,
+			self.rejection_rate() * 100.0,
+			self.shape_probability * 100.0
+		)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Word(String),
+	Op(&'static str),
+	LParen,
+	RParen,
+	Comma,
+}
+
+impl fmt::Display for Token {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Token::Word(w) => write!(f, "{}", w),
+			Token::Op(op) => write!(f, "{}", op),
+			Token::LParen => write!(f, "("),
+			Token::RParen => write!(f, ")"),
+			Token::Comma => write!(f, ","),
+		}
+	}
+}
+
+const OPERATORS: [&str; 12] = [
+	"||", "&&", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "=",
+];
+
+fn tokenize(s: &str) -> Result<Vec<Token>> {
+	let mut tokens = Vec::new();
+	let mut rest = s;
+	while let Some(c) = rest.chars().next() {
+		if c.is_whitespace() {
+			rest = &rest[c.len_utf8()..];
+		} else if c == '#' {
+			rest = rest.find('\n').map_or("", |i| &rest[i..]);
+		} else if c == '(' || c == ')' || c == ',' {
+			tokens.push(match c {
+				'(' => Token::LParen,
+				')' => Token::RParen,
+				_ => Token::Comma,
+			});
+			rest = &rest[1..];
+		} else if c.is_ascii_alphanumeric() || c == '_' {
+			let end = rest
+				.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+				.unwrap_or(rest.len());
+			tokens.push(Token::Word(rest[..end].to_lowercase()));
+			rest = &rest[end..];
+		} else {
+			let op = OPERATORS
+				.iter()
+				.find(|op| rest.starts_with(*op))
+				.ok_or_else(|| {
+					Error::parse(format!("unexpected character '{}' in constraint", c))
+				})?;
+			// a single '=' is accepted as equality
+			tokens.push(Token::Op(if *op == "=" { "==" } else { op }));
+			rest = &rest[op.len()..];
+		}
+	}
+	Ok(tokens)
+}
+
+struct Parser {
+	tokens: Vec<Token>,
+	pos: usize,
+}
+
+impl Parser {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.pos)
+	}
+
+	fn next(&mut self) -> Result<Token> {
+		let token = self
+			.tokens
+			.get(self.pos)
+			.cloned()
+			.ok_or_else(|| Error::parse("unexpected end of constraint"))?;
+		self.pos += 1;
+		Ok(token)
+	}
+
+	fn expect(&mut self, expected: Token) -> Result<()> {
+		let token = self.next()?;
+		if token == expected {
+			Ok(())
+		} else {
+			Err(Error::parse(format!(
+				"expected '{}' but found '{}' in constraint",
+				expected, token
+			)))
+		}
+	}
+
+	fn accept_op(&mut self, ops: &[(&str, Op)]) -> Option<Op> {
+		if let Some(Token::Op(s)) = self.peek() {
+			if let Some((_, op)) = ops.iter().find(|(o, _)| o == s) {
+				self.pos += 1;
+				return Some(*op);
+			}
+		}
+		None
+	}
+
+	fn parse_or(&mut self) -> Result<Expr> {
+		let mut expr = self.parse_and()?;
+		while let Some(op) = self.accept_op(&[("||", Op::Or)]) {
+			expr = Expr::Binary(op, Box::new(expr), Box::new(self.parse_and()?));
+		}
+		Ok(expr)
+	}
+
+	fn parse_and(&mut self) -> Result<Expr> {
+		let mut expr = self.parse_not()?;
+		while let Some(op) = self.accept_op(&[("&&", Op::And)]) {
+			expr = Expr::Binary(op, Box::new(expr), Box::new(self.parse_not()?));
+		}
+		Ok(expr)
+	}
+
+	fn parse_not(&mut self) -> Result<Expr> {
+		if self.peek() == Some(&Token::Op("!")) {
+			self.pos += 1;
+			return Ok(Expr::Not(Box::new(self.parse_not()?)));
+		}
+		self.parse_cmp()
+	}
+
+	fn parse_cmp(&mut self) -> Result<Expr> {
+		let expr = self.parse_sum()?;
+		let ops = [
+			("==", Op::Eq),
+			("!=", Op::Ne),
+			("<", Op::Lt),
+			("<=", Op::Le),
+			(">", Op::Gt),
+			(">=", Op::Ge),
+		];
+		match self.accept_op(&ops) {
+			Some(op) => Ok(Expr::Binary(
+				op,
+				Box::new(expr),
+				Box::new(self.parse_sum()?),
+			)),
+			None => Ok(expr),
+		}
+	}
+
+	fn parse_sum(&mut self) -> Result<Expr> {
+		let mut expr = self.parse_atom()?;
+		while let Some(op) = self.accept_op(&[("+", Op::Add), ("-", Op::Sub)]) {
+			expr = Expr::Binary(op, Box::new(expr), Box::new(self.parse_atom()?));
+		}
+		Ok(expr)
+	}
+
+	fn parse_atom(&mut self) -> Result<Expr> {
+		let word = match self.next()? {
+			Token::LParen => {
+				let expr = self.parse_or()?;
+				self.expect(Token::RParen)?;
+				return Ok(expr);
+			}
+			Token::Word(word) => word,
+			token => {
+				return Err(Error::parse(format!(
+					"unexpected '{}' in constraint",
+					token
+				)))
+			}
+		};
+		if let Ok(n) = word.parse::<i64>() {
+			return Ok(Expr::Num(n));
+		}
+
+		self.expect(Token::LParen)?;
+		let seat = match self.next()? {
+			Token::Word(w) => SeatRef::parse(&w)?,
+			token => {
+				return Err(Error::parse(format!(
+					"expected a seat but found '{}'",
+					token
+				)))
+			}
+		};
+		let expr = match word.as_str() {
+			"hcp" => Expr::Hcp(seat, self.parse_optional_suit()?),
+			"controls" => Expr::Controls(seat, self.parse_optional_suit()?),
+			"spades" => Expr::Length(seat, Suit::Spades),
+			"hearts" => Expr::Length(seat, Suit::Hearts),
+			"diamonds" => Expr::Length(seat, Suit::Diamonds),
+			"clubs" => Expr::Length(seat, Suit::Clubs),
+			"balanced" => Expr::Balanced(seat),
+			"losers" => Expr::Losers(seat),
+			"zar" => Expr::Zar(seat),
+			"cccc" => Expr::Cccc(seat),
+			"quality" => Expr::Quality(seat, self.parse_suit()?),
+			"stopper" => Expr::Stopper(seat, self.parse_suit()?),
+			"shape" => {
+				self.expect(Token::Comma)?;
+				Expr::Shape(seat, self.parse_shape()?)
+			}
+			_ => return Err(Error::parse(format!("unknown function '{}'", word))),
+		};
+		self.expect(Token::RParen)?;
+		Ok(expr)
+	}
+
+	fn parse_suit(&mut self) -> Result<Suit> {
+		match self.parse_optional_suit()? {
+			Some(suit) => Ok(suit),
+			None => Err(Error::parse("expected a suit")),
+		}
+	}
+
+	fn parse_optional_suit(&mut self) -> Result<Option<Suit>> {
+		if self.peek() != Some(&Token::Comma) {
+			return Ok(None);
+		}
+		self.pos += 1;
+		match self.next()? {
+			Token::Word(w) => match w.as_str() {
+				"spades" => Ok(Some(Suit::Spades)),
+				"hearts" => Ok(Some(Suit::Hearts)),
+				"diamonds" => Ok(Some(Suit::Diamonds)),
+				"clubs" => Ok(Some(Suit::Clubs)),
+				_ => Err(Error::parse(format!("invalid suit '{}'", w))),
+			},
+			token => Err(Error::parse(format!(
+				"expected a suit but found '{}'",
+				token
+			))),
+		}
+	}
+
+	fn parse_shape(&mut self) -> Result<Shape> {
+		let mut shape = Shape {
+			include: Vec::new(),
+			exclude: Vec::new(),
+		};
+		let mut exclude = false;
+		loop {
+			let mut word = match self.next()? {
+				Token::Word(w) => w,
+				token => {
+					return Err(Error::parse(format!(
+						"expected a shape but found '{}'",
+						token
+					)))
+				}
+			};
+			let any = word == "any";
+			if any {
+				word = match self.next()? {
+					Token::Word(w) => w,
+					token => {
+						return Err(Error::parse(format!(
+							"expected a shape but found '{}'",
+							token
+						)))
+					}
+				};
+			}
+			let pattern = ShapePattern::parse(any, &word)?;
+			if exclude {
+				shape.exclude.push(pattern);
+			} else {
+				shape.include.push(pattern);
+			}
+			match self.peek() {
+				Some(Token::Op("+")) => exclude = false,
+				Some(Token::Op("-")) => exclude = true,
+				_ => break,
+			}
+			self.pos += 1;
+		}
+		if shape.include.is_empty() {
+			return Err(Error::parse("shape must include at least one pattern"));
+		}
+		Ok(shape)
+	}
+}
@@ -0,0 +1,276 @@
+use super::{Constraint, Generator};
+use crate::{shape::DistPattern, Card, Deal, Rank, Seat, Suit, Vulnerability};
+
+// north: AKQ.JT9.876.5432 (10 hcp, 4333 with four clubs)
+// east: JT98.AKQ.5432.76 (10 hcp, 4-3-4-2)
+// south: 765.8765.AKQ.JT9 (10 hcp, 3-4-3-3)
+// west: 432.432.JT9.AKQ8 (10 hcp, 3-3-3-4)
+fn deal(dealer: Seat) -> Deal {
+	Deal::parse_pbn(
+		"N:AKQ.JT9.876.5432 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8",
+		dealer,
+		Vulnerability::Neither,
+	)
+	.unwrap()
+}
+
+macro_rules! test_constraint_matches {
+    ($($name:ident: $value:expr,)*) => {
+        mod constraint_matches {
+            use super::super::Constraint;
+            use crate::Seat::*;
+        $(
+            #[test]
+            fn $name() {
+                let (input, dealer, expected) = $value;
+                let constraint = Constraint::parse(input).unwrap();
+                assert_eq!(constraint.matches(&super::deal(dealer)), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_constraint_matches! {
+	hcp_eq: ("hcp(north) == 10", North, true),
+	hcp_single_eq: ("hcp(north) = 10", North, true),
+	hcp_range: ("hcp(north) >= 11 && hcp(north) <= 13", North, false),
+	hcp_suit: ("hcp(north, spades) == 9", North, true),
+	hcp_sum: ("hcp(north) + hcp(south) == 20", North, true),
+	controls: ("controls(north) == 3", North, true),
+	controls_suit: ("controls(west, clubs) == 3", North, true),
+	length: ("spades(east) == 4 && clubs(east) < 3", North, true),
+	or: ("spades(north) > 4 || hearts(south) >= 4", North, true),
+	not: ("!balanced(east)", North, false),
+	parens: ("!(hcp(north) > 10 || hcp(south) > 10)", North, true),
+	relative_dealer: ("diamonds(dealer) == 4", East, true),
+	relative_fourth: ("hearts(fourth) == 4", West, true),
+	shape_exact: ("shape(south, 3433)", North, true),
+	shape_exact_wrong_order: ("shape(south, 4333)", North, false),
+	shape_any: ("shape(south, any 4333)", North, true),
+	shape_wildcard: ("shape(east, 4xx2)", North, true),
+	shape_union: ("shape(east, any 5332 + any 4432)", North, true),
+	shape_exclusion: ("shape(east, any 4432 - 4342)", North, false),
+	losers: ("losers(north) == 9 && losers(south) == 9", North, true),
+	zar: ("zar(north) == 21", North, true),
+	cccc: ("cccc(north) == 820", North, true),
+	quality: ("quality(north, spades) == 6", North, true),
+	stopper: ("stopper(south, diamonds) && !stopper(north, hearts)", North, true),
+	comment: ("hcp(north) == 10 # ten points\n && hcp(east) == 10", North, true),
+}
+
+macro_rules! test_constraint_rejects {
+    ($($name:ident: $value:expr,)*) => {
+        mod constraint_rejects {
+            use super::super::Constraint;
+        $(
+            #[test]
+            fn $name() {
+                assert!(Constraint::parse($value).is_err());
+            }
+        )*
+        }
+    }
+}
+
+test_constraint_rejects! {
+	empty: "",
+	unknown_function: "points(north) > 10",
+	unknown_seat: "hcp(nowhere) > 10",
+	unclosed: "(hcp(north) > 10",
+	trailing: "hcp(north) > 10 )",
+	short_shape: "shape(north, 433)",
+	impossible_shape: "shape(north, 5533)",
+	bad_suit: "hcp(north, trumps) > 3",
+	missing_suit: "quality(north) > 5",
+}
+
+#[test]
+fn is_relative() {
+	assert!(Constraint::parse("hcp(first) > 10").unwrap().is_relative());
+	assert!(!Constraint::parse("hcp(north) > 10").unwrap().is_relative());
+}
+
+#[test]
+fn generates_matching_deals() {
+	let constraint = Constraint::parse("hcp(south) >= 12 && spades(south) >= 5").unwrap();
+	let generator = Generator::new(constraint.clone());
+	for _ in 0..10 {
+		let deal = generator.generate().unwrap();
+		assert!(constraint.matches(&deal));
+	}
+}
+
+#[test]
+fn assigns_dealer_for_relative_constraint() {
+	let constraint = Constraint::parse("hcp(dealer) >= 20").unwrap();
+	let deal = Generator::new(constraint).generate().unwrap();
+	assert!(deal.hand_for_seat(deal.dealer).hcp() >= 20);
+}
+
+#[test]
+fn weighs_dealers_by_the_deals_they_allow() {
+	// north cannot deal a balanced hand with seven spades
+	let constraint = Constraint::parse("spades(north) >= 7 && balanced(dealer)").unwrap();
+	let generator = Generator::new(constraint.clone());
+	let (deals, _) = generator
+		.generate_many_with_rng(20, &mut crate::seeded_rng(3))
+		.unwrap();
+	assert!(deals.iter().all(|deal| constraint.matches(deal)));
+	assert!(deals.iter().all(|deal| deal.dealer != Seat::North));
+
+	// five spades with north is far likelier when north is the dealer
+	let constraint = Constraint::parse("spades(north) >= 5 && spades(dealer) >= 5").unwrap();
+	let generator = Generator::new(constraint);
+	let by_dealer: Vec<f64> = Seat::vec()
+		.into_iter()
+		.map(|dealer| generator.lengths(dealer).probability())
+		.collect();
+	let average = by_dealer.iter().sum::<f64>() / 4.0;
+	assert!((generator.shape_probability() - average).abs() < 1e-12);
+	let (deals, _) = generator
+		.generate_many_with_rng(100, &mut crate::seeded_rng(3))
+		.unwrap();
+	let north = deals
+		.iter()
+		.filter(|deal| deal.dealer == Seat::North)
+		.count();
+	let expected = 100.0 * by_dealer[0] / (4.0 * average);
+	assert!(
+		(north as f64 - expected).abs() < 15.0,
+		"{} {}",
+		north,
+		expected
+	);
+}
+
+#[test]
+fn seeded_generation_is_reproducible() {
+	let constraint = Constraint::parse("hcp(north) >= 20").unwrap();
+	let generator = Generator::new(constraint);
+	let deal = generator.generate_from_seed(11).unwrap();
+	assert!(deal.north.hcp() >= 20);
+	assert_eq!(generator.generate_from_seed(11).unwrap(), deal);
+}
+
+#[test]
+fn gives_up_on_impossible_constraint() {
+	// nothing checks controls in advance, so only the attempts run out
+	let constraint = Constraint::parse("controls(north) > 12").unwrap();
+	let result = Generator::new(constraint).with_max_attempts(100).generate();
+	assert!(result.is_err());
+}
+
+#[test]
+fn reports_impossible_points() {
+	for text in [
+		"hcp(north) > 37",
+		"hcp(north) >= 21 && hcp(south) >= 20",
+		"hcp(dealer) < 5 && hcp(second) < 5 && hcp(third) < 5 && hcp(fourth) < 5",
+		"!(hcp(east) <= 37)",
+	] {
+		let generator = Generator::new(Constraint::parse(text).unwrap());
+		assert_eq!(generator.shape_probability(), 1.0);
+		assert!(generator.generate().is_err(), "{}", text);
+	}
+
+	// while these can be shared out
+	let constraint = Constraint::parse("hcp(north) >= 10 && hcp(south) >= 10").unwrap();
+	let deal = Generator::new(constraint.clone())
+		.generate_from_seed(1)
+		.unwrap();
+	assert!(constraint.matches(&deal));
+}
+
+#[test]
+fn first_seat_one_nt_opener() {
+	let deal = Deal::first_seat_one_nt_opener();
+	let opener = deal.hand_for_seat(deal.dealer);
+	assert!(opener.distribution().is_balanced());
+	assert!(opener.hcp() >= 15 && opener.hcp() <= 17);
+}
+
+#[test]
+fn generates_rare_shapes_quickly() {
+	let constraint =
+		Constraint::parse("shape(south, any 74xx) && hcp(south) >= 11 && hcp(south) <= 13")
+			.unwrap();
+	let generator = Generator::new(constraint.clone()).with_max_attempts(1000);
+	assert!(generator.shape_probability() < 0.01);
+	let (deals, stats) = generator
+		.generate_many_with_rng(20, &mut crate::seeded_rng(8))
+		.unwrap();
+	assert_eq!(deals.len(), 20);
+	assert!(deals.iter().all(|deal| constraint.matches(deal)));
+	assert_eq!(stats.matched, 20);
+	assert!(stats.dealt >= 20);
+	assert!(stats.rejection_rate() < 1.0);
+	assert!(stats.to_string().starts_with("20 of "));
+}
+
+#[test]
+fn reports_impossible_shapes() {
+	let constraint = Constraint::parse("spades(north) >= 7 && spades(south) >= 7").unwrap();
+	let generator = Generator::new(constraint);
+	assert_eq!(generator.shape_probability(), 0.0);
+	assert!(generator.generate().is_err());
+}
+
+#[test]
+fn reads_shapes_through_boolean_operators() {
+	// either north is short in spades or south is, so neither is certain
+	let either = Constraint::parse("spades(north) <= 1 || spades(south) <= 1").unwrap();
+	let generator = Generator::new(either.clone());
+	assert!(generator.shape_probability() > 0.0);
+	for seed in 0..10 {
+		assert!(either.matches(&generator.generate_from_seed(seed).unwrap()));
+	}
+
+	let not = Constraint::parse("!(hearts(east) < 6) && !balanced(west)").unwrap();
+	let generator = Generator::new(not.clone());
+	assert!(generator.shape_probability() < 0.2);
+	for seed in 0..10 {
+		assert!(not.matches(&generator.generate_from_seed(seed).unwrap()));
+	}
+}
+
+#[test]
+fn shape_first_deals_are_unbiased() {
+	// the chance of north holding the spade ace with a five-card suit is 5/13
+	let constraint = Constraint::parse("spades(north) == 5").unwrap();
+	let generator = Generator::new(constraint);
+	let (deals, _) = generator
+		.generate_many_with_rng(2000, &mut crate::seeded_rng(2))
+		.unwrap();
+	let aces = deals
+		.iter()
+		.filter(|deal| {
+			deal.north.contains(Card {
+				rank: Rank::Ace,
+				suit: Suit::Spades,
+			})
+		})
+		.count();
+	let share = aces as f64 / deals.len() as f64;
+	assert!((share - 5.0 / 13.0).abs() < 0.04, "{}", share);
+}
+
+#[test]
+fn shapes_read_as_dist_patterns() {
+	let pairs = [
+		("any 5xx4", "5-4-any-any"),
+		("54xx", "5=4=any=any"),
+		("any 4333", "4-3-3-3"),
+	];
+	for (shape, pattern) in pairs {
+		let constraint = Constraint::parse(&format!("shape(north, {})", shape)).unwrap();
+		let pattern = DistPattern::parse(pattern).unwrap();
+		for seed in 0..200 {
+			let deal = Deal::from_seed(seed);
+			assert_eq!(
+				constraint.matches(&deal),
+				pattern.matches(&deal.hand_for_seat(Seat::North).distribution())
+			);
+		}
+	}
+}
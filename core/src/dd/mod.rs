@@ -0,0 +1,783 @@
+//! Double-dummy analysis: the number of tricks each side takes when every
+//! player can see all four hands and plays perfectly.
+
+use super::{Deal, Seat, Suit, Trump};
+use std::{
+	cmp,
+	collections::HashMap,
+	fmt,
+	hash::{BuildHasherDefault, Hasher},
+};
+
+#[cfg(test)]
+mod tests;
+
+const STRAINS: [Trump; 5] = [
+	Trump::NoTrump,
+	Trump::Trump(Suit::Spades),
+	Trump::Trump(Suit::Hearts),
+	Trump::Trump(Suit::Diamonds),
+	Trump::Trump(Suit::Clubs),
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tricks {
+	pub ns: usize,
+	pub ew: usize,
+}
+
+impl Tricks {
+	pub fn for_seat(&self, seat: Seat) -> usize {
+		match seat {
+			Seat::North | Seat::South => self.ns,
+			Seat::East | Seat::West => self.ew,
+		}
+	}
+}
+
+pub fn solve(deal: &Deal, trump: Trump, leader: Seat) -> Tricks {
+	Solver::new(deal, trump).solve(leader)
+}
+
+// tricks available to each declarer in each strain, with declarer's left-hand
+// opponent on lead
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Table {
+	tricks: [[usize; 5]; 4],
+}
+
+impl Table {
+	pub fn new(deal: &Deal) -> Self {
+		let mut tricks = [[0; 5]; 4];
+		for (strain_idx, trump) in STRAINS.iter().enumerate() {
+			// positions are shared between leaders, so one solver per strain
+			// lets the transposition table carry over
+			let mut solver = Solver::new(deal, *trump);
+			for declarer in Seat::vec() {
+				let leader = declarer.lho();
+				tricks[declarer.index()][strain_idx] = solver.solve(leader).for_seat(declarer);
+			}
+		}
+		Table { tricks }
+	}
+
+	// a table from known results, one row per declarer from north clockwise
+	// with strains in the order of `strains()`
+	pub fn from_tricks(tricks: [[usize; 5]; 4]) -> Self {
+		Table { tricks }
+	}
+
+	pub fn tricks(&self, declarer: Seat, trump: Trump) -> usize {
+		let strain_idx = STRAINS.iter().position(|t| *t == trump).unwrap();
+		self.tricks[declarer.index()][strain_idx]
+	}
+
+	pub fn strains() -> &'static [Trump] {
+		&STRAINS
+	}
+}
+
+impl fmt::Display for Table {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "+----+----+----+----+----+----+")?;
+		writeln!(f, "|    | NT | S  | H  | D  | C  |")?;
+		writeln!(f, "+----+----+----+----+----+----+")?;
+		for seat in Seat::vec() {
+			write!(f, "| {}  |", seat.to_pbn())?;
+			for tricks in self.tricks[seat.index()].iter() {
+				write!(f, " {:<3}|", tricks)?;
+			}
+			writeln!(f)?;
+		}
+		writeln!(f, "+----+----+----+----+----+----+")
+	}
+}
+
+#[derive(Copy, Clone)]
+struct Played {
+	seat: usize,
+	suit: usize,
+	rank: usize,
+}
+
+// transposition table keys are already well mixed, so a multiply-fold hash is
+// much cheaper than the default hasher with no loss in distribution
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for b in bytes {
+			self.0 = (self.0.rotate_left(5) ^ *b as u64).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+		}
+	}
+
+	fn write_u128(&mut self, n: u128) {
+		let folded = (n as u64) ^ ((n >> 64) as u64).rotate_left(32);
+		let mixed = (self.0.rotate_left(5) ^ folded).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+		self.0 = mixed ^ mixed >> 29;
+	}
+}
+
+// cards by suit, with bit n set for rank n (two is 0)
+type Ranks = [u16; 4];
+
+// the owners of each suit's remaining cards from the top down, two bits per
+// card, with each suit in its own 26-bit field starting from the top
+struct Position {
+	bucket: u128,
+	owners: u128,
+}
+
+// a bound on the tricks north-south take from a trick boundary. Only the top
+// `counts[suit]` cards of each suit are pinned to their owners, and the rest
+// are spot cards that did not affect the result, so the bound holds for every
+// position with the same suit lengths that agrees on the owners under `mask`.
+struct Entry {
+	mask: u128,
+	owners: u128,
+	counts: [u8; 4],
+	lower: u8,
+	upper: u8,
+	// the lead that last decided the search, tried first on a revisit
+	best: (u8, u8),
+}
+
+struct Solver {
+	hands: [Ranks; 4],
+	trump: Option<usize>,
+	// entries grouped by leader and the length of every suit in every hand
+	tt: HashMap<u128, Vec<Entry>, BuildHasherDefault<KeyHasher>>,
+	// north-south's tricks from the previous solve
+	last: Option<usize>,
+}
+
+impl Solver {
+	fn new(deal: &Deal, trump: Trump) -> Self {
+		let mut hands = [[0; 4]; 4];
+		for seat in Seat::vec() {
+			let cards = deal.hand_for_seat(seat).cards();
+			for suit in [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
+				hands[seat.index()][suit as usize] = cards.holding(suit);
+			}
+		}
+		let trump = match trump {
+			Trump::NoTrump => None,
+			Trump::Trump(suit) => Some(suit as usize),
+		};
+		Solver {
+			hands,
+			trump,
+			tt: HashMap::default(),
+			last: None,
+		}
+	}
+
+	fn solve(&mut self, leader: Seat) -> Tricks {
+		let total = self.tricks_left(leader.index());
+		let trick = [Played {
+			seat: 0,
+			suit: 0,
+			rank: 0,
+		}; 4];
+		// results for different leaders are rarely far apart, so step from the
+		// last one rather than bisecting when there is one
+		let (mut lo, mut hi) = (0, total);
+		let mut guess = self.last.map(|n| n.min(total));
+		while lo < hi {
+			let target = match guess {
+				Some(n) => n.max(lo + 1).min(hi),
+				None => (lo + hi + 1) / 2,
+			};
+			if self.search(leader.index(), trick, 0, target as i32).0 {
+				lo = target;
+				guess = guess.map(|_| target + 1);
+			} else {
+				hi = target - 1;
+				guess = guess.map(|_| target - 1);
+			}
+		}
+		self.last = Some(lo);
+		Tricks {
+			ns: lo,
+			ew: total - lo,
+		}
+	}
+
+	fn tricks_left(&self, seat: usize) -> usize {
+		self.hands[seat]
+			.iter()
+			.map(|m| m.count_ones() as usize)
+			.sum()
+	}
+
+	fn remaining(&self, suit: usize) -> u16 {
+		self.hands.iter().fold(0, |m, h| m | h[suit])
+	}
+
+	// whether north-south can take at least `target` of the remaining tricks,
+	// along with the ranks that decided it
+	fn search(
+		&mut self,
+		leader: usize,
+		mut trick: [Played; 4],
+		played: usize,
+		target: i32,
+	) -> (bool, Ranks) {
+		// positions are only cached at trick boundaries
+		let mut position = None;
+		let mut best = None;
+		if played == 0 {
+			if let Some(result) = self.cutoff(leader, target) {
+				return result;
+			}
+			let pos = self.position(leader);
+			if let Some(result) = self.lookup(&pos, target, &mut best) {
+				return result;
+			}
+			position = Some(pos);
+		}
+
+		let seat = (leader + played) % 4;
+		let maximizing = seat % 2 == 0;
+		let mut moves = [(0, 0, 0); 13];
+		let count = self.moves(seat, &trick[..played], &mut moves);
+		if let Some((suit, rank)) = best {
+			let suit = suit as usize;
+			let rank = rank as usize;
+			if let Some(idx) = moves[..count]
+				.iter()
+				.position(|m| m.1 == suit && m.2 == rank)
+			{
+				moves[..=idx].rotate_right(1);
+			}
+		}
+		let mut decided = None;
+		let mut result = !maximizing;
+		let mut relevant = [0; 4];
+		for &(_, suit, rank) in &moves[..count] {
+			self.hands[seat][suit] &= !(1 << rank);
+			trick[played] = Played { seat, suit, rank };
+			let (outcome, ranks) = if played == 3 {
+				let (winner, decided_by_rank) = self.winner(&trick);
+				let won = if winner.seat % 2 == 0 { 1 } else { 0 };
+				let (outcome, mut ranks) = self.search(winner.seat, trick, 0, target - won);
+				if decided_by_rank {
+					ranks[winner.suit] |= 1 << winner.rank;
+				}
+				(outcome, ranks)
+			} else {
+				self.search(leader, trick, played + 1, target)
+			};
+			self.hands[seat][suit] |= 1 << rank;
+			if outcome == maximizing {
+				result = outcome;
+				relevant = ranks;
+				decided = Some((suit as u8, rank as u8));
+				break;
+			}
+			for s in 0..4 {
+				relevant[s] |= ranks[s];
+			}
+		}
+		if decided.is_none() {
+			// cards skipped as equivalent to a higher one stay equivalent only
+			// while the cards between them keep their owners
+			let mut tried = [0; 4];
+			for &(_, suit, rank) in &moves[..count] {
+				tried[suit] |= 1 << rank;
+			}
+			for s in 0..4 {
+				if tried[s] != 0 {
+					relevant[s] |= self.hands[seat][s] & !tried[s];
+				}
+			}
+		}
+
+		if let Some(pos) = position {
+			self.store(pos, &relevant, result, target as u8, decided);
+		}
+		(result, relevant)
+	}
+
+	// results that follow from the trick count alone or from tricks that can be
+	// cashed from the top, without searching
+	fn cutoff(&self, leader: usize, target: i32) -> Option<(bool, Ranks)> {
+		let left = self.tricks_left(leader) as i32;
+		if target <= 0 {
+			return Some((true, [0; 4]));
+		}
+		if target > left {
+			return Some((false, [0; 4]));
+		}
+		// top trumps always make, whoever is on lead
+		if let Some((ns, ew, ranks)) = self.sure_trumps() {
+			if ns >= target {
+				return Some((true, ranks));
+			}
+			if target > left - ew {
+				return Some((false, ranks));
+			}
+		}
+		if leader % 2 == 0 {
+			if let Some(ranks) = self.quick_tricks(leader, target as usize) {
+				return Some((true, ranks));
+			}
+		} else if let Some(ranks) = self.quick_tricks(leader, (left - target + 1) as usize) {
+			return Some((false, ranks));
+		}
+		None
+	}
+
+	// a stored bound that decides the search for `target`, noting the stored
+	// best lead of the first matching entry either way
+	fn lookup(
+		&mut self,
+		pos: &Position,
+		target: i32,
+		best: &mut Option<(u8, u8)>,
+	) -> Option<(bool, Ranks)> {
+		let entries = self.tt.get_mut(&pos.bucket)?;
+		let mut found = None;
+		for (idx, entry) in entries.iter().enumerate() {
+			if pos.owners & entry.mask != entry.owners {
+				continue;
+			}
+			if best.is_none() {
+				*best = Some(entry.best);
+			}
+			if entry.lower as i32 >= target {
+				found = Some((idx, true));
+				break;
+			}
+			if (entry.upper as i32) < target {
+				found = Some((idx, false));
+				break;
+			}
+		}
+		let (idx, result) = found?;
+		// keep entries that cut off near the front
+		let counts = entries[idx].counts;
+		entries[..=idx].rotate_right(1);
+		Some((result, self.top_cards(&counts)))
+	}
+
+	fn store(
+		&mut self,
+		pos: Position,
+		relevant: &Ranks,
+		result: bool,
+		target: u8,
+		best: Option<(u8, u8)>,
+	) {
+		let mut counts = [0; 4];
+		for s in 0..4 {
+			if relevant[s] != 0 {
+				// pin every card at least as high as the lowest relevant one
+				let lowest = relevant[s].trailing_zeros();
+				counts[s] = (self.remaining(s) >> lowest).count_ones() as u8;
+			}
+		}
+		let mut mask = 0;
+		for (s, count) in counts.iter().enumerate() {
+			let bits = (1u128 << (2 * count)) - 1;
+			mask |= bits << (26 - 2 * count) << (26 * (3 - s));
+		}
+		let owners = pos.owners & mask;
+		let entries = self.tt.entry(pos.bucket).or_default();
+		let entry = match entries
+			.iter()
+			.position(|e| e.mask == mask && e.owners == owners)
+		{
+			Some(idx) => &mut entries[idx],
+			None => {
+				entries.push(Entry {
+					mask,
+					owners,
+					counts,
+					lower: 0,
+					upper: 13,
+					best: (0, 0),
+				});
+				entries.last_mut().unwrap()
+			}
+		};
+		if let Some(best) = best {
+			entry.best = best;
+		}
+		if result {
+			entry.lower = entry.lower.max(target);
+		} else {
+			entry.upper = entry.upper.min(target - 1);
+		}
+	}
+
+	fn position(&self, leader: usize) -> Position {
+		let mut bucket = leader as u128;
+		for hand in &self.hands {
+			for suit in hand {
+				bucket = bucket << 4 | suit.count_ones() as u128;
+			}
+		}
+		let mut owners = 0;
+		for s in 0..4 {
+			// the two bits of each card's owner
+			let low = self.hands[1][s] | self.hands[3][s];
+			let high = self.hands[2][s] | self.hands[3][s];
+			let mut all = self.remaining(s);
+			let mut suit = 0u32;
+			let mut shift = 26;
+			while all != 0 {
+				let rank = 15 - all.leading_zeros();
+				shift -= 2;
+				suit |= (((low >> rank & 1) | (high >> rank & 1) << 1) as u32) << shift;
+				all &= !(1 << rank);
+			}
+			owners = owners << 26 | suit as u128;
+		}
+		Position { bucket, owners }
+	}
+
+	// the highest `counts[suit]` remaining cards of each suit
+	fn top_cards(&self, counts: &[u8; 4]) -> Ranks {
+		let mut ranks = [0; 4];
+		for s in 0..4 {
+			let mut all = self.remaining(s);
+			for _ in 0..counts[s] {
+				let bit = 1 << (15 - all.leading_zeros());
+				ranks[s] |= bit;
+				all &= !bit;
+			}
+		}
+		ranks
+	}
+
+	// the winning card of a complete trick, and whether it won by rank rather
+	// than by being the only card of its suit
+	fn winner(&self, trick: &[Played; 4]) -> (Played, bool) {
+		let mut best = trick[0];
+		for card in &trick[1..] {
+			let beats = if card.suit == best.suit {
+				card.rank > best.rank
+			} else {
+				Some(card.suit) == self.trump
+			};
+			if beats {
+				best = *card;
+			}
+		}
+		let same_suit = trick.iter().filter(|c| c.suit == best.suit).count();
+		(best, same_suit > 1)
+	}
+
+	fn suit_len(&self, seat: usize, suit: usize) -> usize {
+		self.hands[seat][suit].count_ones() as usize
+	}
+
+	// trumps above every trump the other side holds, which always make; only
+	// one hand counts, since partners' trumps may fall on the same trick
+	fn sure_trumps(&self) -> Option<(i32, i32, Ranks)> {
+		let trump = self.trump?;
+		let ns = self.hands[0][trump] | self.hands[2][trump];
+		let ew = self.hands[1][trump] | self.hands[3][trump];
+		let (side, run) = if ns > ew {
+			(0, ns & !below(ew))
+		} else if ew != 0 {
+			(1, ew & !below(ns))
+		} else {
+			return None;
+		};
+		let held = cmp::max_by_key(
+			self.hands[side][trump] & run,
+			self.hands[side + 2][trump] & run,
+			|h| h.count_ones(),
+		);
+		let sure = held.count_ones() as i32;
+		let mut ranks = [0; 4];
+		ranks[trump] = held;
+		match side {
+			0 => Some((sure, 0, ranks)),
+			_ => Some((0, sure, ranks)),
+		}
+	}
+
+	// whether the leader's side can cash `need` tricks from the top without
+	// giving up the lead, and if so the top cards that relies on
+	fn quick_tricks(&self, leader: usize, need: usize) -> Option<Ranks> {
+		let partner = (leader + 2) % 4;
+		let opps = [(leader + 1) % 4, (leader + 3) % 4];
+
+		// only suits where the side holds the top card can contribute, and
+		// no more than the longer hand's length
+		let mut most = 0;
+		for s in 0..4 {
+			let ours = self.hands[leader][s] | self.hands[partner][s];
+			if ours > (self.hands[opps[0]][s] | self.hands[opps[1]][s]) {
+				most += cmp::max(self.suit_len(leader, s), self.suit_len(partner, s));
+			}
+		}
+		if most < need {
+			return None;
+		}
+
+		let ruffing = self
+			.trump
+			.filter(|t| opps.iter().any(|o| self.hands[*o][*t] != 0));
+
+		// tricks `seat` can cash in a suit while `other` follows or discards
+		let cash = |seat: usize, other: usize, suit: usize| {
+			let own = self.hands[seat][suit];
+			let cards = own & !below(self.remaining(suit) & !own);
+			let tops = cards.count_ones() as usize;
+			let opp_len = opps.iter().map(|o| self.suit_len(*o, suit)).max().unwrap();
+			let mut n = if tops >= opp_len && self.suit_len(other, suit) <= tops {
+				self.suit_len(seat, suit)
+			} else {
+				tops
+			};
+			if let Some(trump) = ruffing.filter(|t| *t != suit) {
+				// opponents with trumps ruff once they run out of the suit,
+				// and a partner left with only trumps would take over the lead
+				for o in opps.iter().filter(|o| self.hands[**o][trump] != 0) {
+					n = n.min(self.suit_len(*o, suit));
+				}
+				if self.hands[other][trump] != 0 {
+					n = n.min(self.suit_len(other, suit));
+				}
+			}
+			(n, cards)
+		};
+
+		let own = [0, 1, 2, 3].map(|s| cash(leader, partner, s));
+		if let Some(ranks) = fewest_suits(&own, need) {
+			return Some(ranks);
+		}
+
+		// otherwise the leader can cash what partner follows to, then cross to
+		// partner's top cards with a low card and run them
+		for s in 0..4 {
+			if own[s].0 != 0 || self.hands[leader][s] == 0 || self.hands[partner][s] == 0 {
+				continue;
+			}
+			let first_trick = ruffing.map_or(true, |t| {
+				t == s
+					|| opps
+						.iter()
+						.all(|o| self.hands[*o][t] == 0 || self.hands[*o][s] != 0)
+			});
+			if !first_trick {
+				continue;
+			}
+			let mut tricks = own;
+			tricks[s] = cash(partner, leader, s);
+			for t in (0..4).filter(|t| *t != s) {
+				if own[t].0 > self.suit_len(partner, t) {
+					tricks[t] = (0, 0);
+				}
+			}
+			if let Some(ranks) = fewest_suits(&tricks, need) {
+				return Some(ranks);
+			}
+		}
+		None
+	}
+
+	// every suit's remaining cards, including those in the current trick
+	fn suit_cards(&self, suit: usize, trick: &[Played]) -> u16 {
+		let in_trick = trick
+			.iter()
+			.filter(|p| p.suit == suit)
+			.fold(0, |m, p| m | 1 << p.rank);
+		in_trick | self.remaining(suit)
+	}
+
+	// the highest rank of a suit held by a seat, if any
+	fn highest(&self, seat: usize, suit: usize) -> Option<usize> {
+		match self.hands[seat][suit] {
+			0 => None,
+			m => Some(15 - m.leading_zeros() as usize),
+		}
+	}
+
+	// fills `moves` with candidate cards for the seat to play, skipping cards
+	// equivalent to a higher one in the same hand, in a rough best-first order
+	fn moves(&self, seat: usize, trick: &[Played], moves: &mut [(i32, usize, usize); 13]) -> usize {
+		let hand = self.hands[seat];
+		let partner = (seat + 2) % 4;
+		let lho = (seat + 1) % 4;
+		let rho = (seat + 3) % 4;
+		let ruffs = |seat: usize, suit: usize| {
+			self.trump.is_some_and(|t| {
+				t != suit && self.hands[seat][suit] == 0 && self.hands[seat][t] != 0
+			})
+		};
+
+		let lead = match trick.first() {
+			Some(lead) => *lead,
+			None => {
+				let mut count = 0;
+				for suit in (0..4).filter(|s| hand[*s] != 0) {
+					let all = self.remaining(suit);
+					let top = 15 - all.leading_zeros() as usize;
+					let lho_ruffs = ruffs(lho, suit);
+					let class = if lho_ruffs {
+						10
+					} else if self.hands[partner][suit] & (1 << top) != 0 {
+						// low toward partner's winner
+						50
+					} else if ruffs(partner, suit) {
+						65
+					} else if self.hands[lho][suit] & (1 << top) != 0 {
+						// lead through the top card
+						25
+					} else if self.hands[rho][suit] & (1 << top) != 0 {
+						15
+					} else {
+						20
+					};
+					for rank in distinct(hand[suit], all) {
+						let r = rank as i32;
+						let score = if rank == top && !lho_ruffs {
+							// cash a winner
+							60 * 16 + r
+						} else {
+							class * 16 - r
+						};
+						moves[count] = (score, suit, rank);
+						count += 1;
+					}
+				}
+				moves[..count].sort_unstable_by_key(|m| cmp::Reverse(m.0));
+				return count;
+			}
+		};
+
+		let led = lead.suit;
+		let mut current = lead;
+		for p in &trick[1..] {
+			let beats = if p.suit == current.suit {
+				p.rank > current.rank
+			} else {
+				Some(p.suit) == self.trump
+			};
+			if beats {
+				current = *p;
+			}
+		}
+		// whether the opponent still to play after this seat, if any, can
+		// beat a card
+		let lho_to_play = trick.len() < 3;
+		let lho_led = self.highest(lho, led);
+		let lho_trump = match self.trump {
+			Some(t) if t != led && lho_led.is_none() => self.highest(lho, t),
+			_ => None,
+		};
+		let lho_beats = |suit: usize, rank: usize| {
+			lho_to_play
+				&& match (lho_led, lho_trump) {
+					(Some(high), _) => suit == led && high > rank,
+					(None, Some(high)) => Some(suit) != self.trump || high > rank,
+					(None, None) => false,
+				}
+		};
+		let partner_winning = current.seat == partner && !lho_beats(current.suit, current.rank);
+		// second hand low when partner can win
+		let partner_wins_later = trick.len() == 1 && !lho_beats(current.suit, current.rank) && {
+			let following = match self.highest(partner, led) {
+				Some(high) => current.suit == led && high > current.rank,
+				None => false,
+			};
+			following || (current.suit != self.trump.unwrap_or(4) && ruffs(partner, led))
+		};
+
+		let (first_suit, last_suit) = match hand[led] {
+			0 => (0, 3),
+			_ => (led, led),
+		};
+		let mut count = 0;
+		for suit in (first_suit..=last_suit).filter(|s| hand[*s] != 0) {
+			let all = self.suit_cards(suit, trick);
+			let top = 15 - all.leading_zeros() as usize;
+			let len = hand[suit].count_ones() as i32;
+			for rank in distinct(hand[suit], all) {
+				let r = rank as i32;
+				let beats = if suit == current.suit {
+					rank > current.rank
+				} else {
+					Some(suit) == self.trump
+				};
+				let class = if suit != led && !beats {
+					// discard from long suits without winners
+					if rank == top {
+						0
+					} else {
+						10 + len
+					}
+				} else if partner_winning {
+					if beats && suit != led {
+						0
+					} else {
+						40
+					}
+				} else if beats && !lho_beats(suit, rank) {
+					60
+				} else if partner_wins_later {
+					45
+				} else if beats {
+					30
+				} else {
+					35
+				};
+				moves[count] = (class * 16 - r, suit, rank);
+				count += 1;
+			}
+		}
+		moves[..count].sort_unstable_by_key(|m| cmp::Reverse(m.0));
+		count
+	}
+}
+
+// the top cards of the fewest suits that together hold `need` of the given
+// tricks per suit, if there are that many
+fn fewest_suits(tricks: &[(usize, u16); 4], need: usize) -> Option<Ranks> {
+	let mut order = [0, 1, 2, 3];
+	order.sort_unstable_by_key(|s| cmp::Reverse(tricks[*s].0));
+	let mut ranks = [0; 4];
+	let mut total = 0;
+	for s in order.iter() {
+		if total >= need {
+			break;
+		}
+		total += tricks[*s].0;
+		ranks[*s] = tricks[*s].1;
+	}
+	if total >= need {
+		Some(ranks)
+	} else {
+		None
+	}
+}
+
+// every rank up to and including the highest in `cards`
+fn below(cards: u16) -> u16 {
+	match cards {
+		0 => 0,
+		_ => u16::MAX >> cards.leading_zeros(),
+	}
+}
+
+// the ranks held in `hand`, skipping any equivalent to the rank above it
+// since no other card of the suit in `all` lies between them
+fn distinct(hand: u16, all: u16) -> impl Iterator<Item = usize> {
+	(0..13)
+		.rev()
+		.filter(move |rank| hand & (1 << rank) != 0)
+		.filter(move |rank| {
+			let above = all & !below(1 << rank);
+			above == 0 || hand & (1 << above.trailing_zeros()) == 0
+		})
+}
@@ -0,0 +1,185 @@
+use super::{solve, Played, Solver, Table, Tricks};
+use crate::{Deal, Hand, Seat, Suit, Trump, Vulnerability};
+use rand::seq::SliceRandom;
+
+fn deal(north: &str, east: &str, south: &str, west: &str) -> Deal {
+	Deal {
+		dealer: Seat::North,
+		vulnerable: Vulnerability::Neither,
+		north: Hand::parse_partial(north).unwrap(),
+		east: Hand::parse_partial(east).unwrap(),
+		south: Hand::parse_partial(south).unwrap(),
+		west: Hand::parse_partial(west).unwrap(),
+	}
+}
+
+// each player holds a whole suit
+fn one_suit_each() -> Deal {
+	deal(
+		"AKQJT98765432|||",
+		"|AKQJT98765432||",
+		"||AKQJT98765432|",
+		"|||AKQJT98765432",
+	)
+}
+
+// plays out every card with no pruning at all
+fn brute_force(solver: &mut Solver, leader: usize, mut trick: [Played; 4], played: usize) -> usize {
+	if played == 0 && solver.tricks_left(leader) == 0 {
+		return 0;
+	}
+	let seat = (leader + played) % 4;
+	let hand = solver.hands[seat];
+	let suits: Vec<usize> = match played {
+		0 => (0..4).collect(),
+		_ if hand[trick[0].suit] != 0 => vec![trick[0].suit],
+		_ => (0..4).collect(),
+	};
+	let mut results = Vec::new();
+	for suit in suits {
+		for rank in 0..13 {
+			if hand[suit] & (1 << rank) == 0 {
+				continue;
+			}
+			solver.hands[seat][suit] &= !(1 << rank);
+			trick[played] = Played { seat, suit, rank };
+			let tricks = if played == 3 {
+				let (winner, _) = solver.winner(&trick);
+				let won = if winner.seat % 2 == 0 { 1 } else { 0 };
+				won + brute_force(solver, winner.seat, trick, 0)
+			} else {
+				brute_force(solver, leader, trick, played + 1)
+			};
+			solver.hands[seat][suit] |= 1 << rank;
+			results.push(tricks);
+		}
+	}
+	if seat % 2 == 0 {
+		*results.iter().max().unwrap()
+	} else {
+		*results.iter().min().unwrap()
+	}
+}
+
+#[test]
+fn one_suit_each_no_trump() {
+	let deal = one_suit_each();
+	assert_eq!(
+		solve(&deal, Trump::NoTrump, Seat::North),
+		Tricks { ns: 13, ew: 0 }
+	);
+	assert_eq!(
+		solve(&deal, Trump::NoTrump, Seat::East),
+		Tricks { ns: 0, ew: 13 }
+	);
+}
+
+#[test]
+fn one_suit_each_ruffing() {
+	let deal = one_suit_each();
+	let spades = Trump::Trump(Suit::Spades);
+	assert_eq!(solve(&deal, spades, Seat::East), Tricks { ns: 13, ew: 0 });
+	let clubs = Trump::Trump(Suit::Clubs);
+	assert_eq!(solve(&deal, clubs, Seat::South), Tricks { ns: 0, ew: 13 });
+}
+
+#[test]
+fn simple_squeeze() {
+	// west guards both majors and must discard in front of north
+	let squeeze = deal("A2|Q||", "||543|", "4|2||A", "K3|K||");
+	assert_eq!(solve(&squeeze, Trump::NoTrump, Seat::South).ns, 3);
+
+	// west can lead a spade to break up the squeeze
+	assert_eq!(solve(&squeeze, Trump::NoTrump, Seat::West).ns, 1);
+}
+
+#[test]
+fn equivalent_cards_in_transposition_table() {
+	// later positions match stored ones where west's trumps were equivalent,
+	// which they are not once east's eight sits between them
+	let ending = deal("6|8||T6", "3|2|8|K", "42||6|3", "|4|T95|");
+	let diamonds = Trump::Trump(Suit::Diamonds);
+	assert_eq!(solve(&ending, diamonds, Seat::South).ew, 4);
+}
+
+// the first cards of a shuffled deck, the same number to each seat
+fn ending(seed: u64, size: usize) -> Deal {
+	let mut deck = crate::Deck::new().0;
+	deck.shuffle(&mut crate::seeded_rng(seed));
+	let hand = |i: usize| Hand::new(deck[i * size..(i + 1) * size].to_vec());
+	Deal {
+		dealer: Seat::North,
+		vulnerable: Vulnerability::Neither,
+		north: hand(0),
+		east: hand(1),
+		south: hand(2),
+		west: hand(3),
+	}
+}
+
+fn brute_force_from(ending: &Deal, trump: Trump, leader: Seat) -> usize {
+	let mut solver = Solver::new(ending, trump);
+	let trick = [Played {
+		seat: 0,
+		suit: 0,
+		rank: 0,
+	}; 4];
+	brute_force(&mut solver, leader.index(), trick, 0)
+}
+
+#[test]
+fn matches_brute_force_on_endings() {
+	for seed in 0..20 {
+		let ending = ending(seed, 1 + seed as usize % 4);
+		for trump in Table::strains() {
+			for leader in Seat::vec() {
+				let expected = brute_force_from(&ending, *trump, leader);
+				assert_eq!(solve(&ending, *trump, leader).ns, expected);
+			}
+		}
+	}
+}
+
+#[test]
+fn reused_solver_matches_brute_force() {
+	// one solver answers every leader in turn, as `Table::new` uses it, so
+	// positions stored for one leader are found again for the next. Brute
+	// force over five cards each is only quick for some endings.
+	let ending = ending(3, 5);
+	for trump in Table::strains() {
+		let mut solver = Solver::new(&ending, *trump);
+		for leader in Seat::vec() {
+			let expected = brute_force_from(&ending, *trump, leader);
+			assert_eq!(solver.solve(leader).ns, expected);
+		}
+	}
+}
+
+#[test]
+fn table_matches_single_solves() {
+	// eight-card endings keep this quick in debug builds
+	let ending = ending(7, 8);
+	let table = Table::new(&ending);
+	for trump in Table::strains() {
+		// the opening leader is declarer's left-hand opponent
+		let tricks = solve(&ending, *trump, Seat::East);
+		assert_eq!(tricks.ns + tricks.ew, 8);
+		assert_eq!(table.tricks(Seat::North, *trump), tricks.ns);
+		assert_eq!(
+			table.tricks(Seat::East, *trump),
+			solve(&ending, *trump, Seat::South).ew
+		);
+	}
+}
+
+#[test]
+fn full_deal() {
+	let deal = Deal::parse_pbn(
+		"N:Q95.865.853.J765 AJ3.KT42.AK.AKQ9 4.AQJ7.Q942.T832 KT8762.93.JT76.4",
+		Seat::North,
+		Vulnerability::Neither,
+	)
+	.unwrap();
+	assert_eq!(solve(&deal, Trump::Trump(Suit::Spades), Seat::South).ew, 12);
+	assert_eq!(solve(&deal, Trump::NoTrump, Seat::North).ew, 9);
+}
@@ -0,0 +1,239 @@
+//! Hand evaluation beyond the high card count: losers, controls and quick
+//! tricks, distributional points, the Kaplan-Rubens and Zar point counts, and
+//! the quality and stoppers of single suits.
+
+use super::{Hand, Rank, Suit};
+
+#[cfg(test)]
+mod tests;
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+// the cards a hand holds in one suit, a bit for each rank from the two up
+struct Holding {
+	length: usize,
+	ranks: u16,
+}
+
+impl Holding {
+	fn new(hand: &Hand, suit: Suit) -> Self {
+		let ranks = hand.0.holding(suit);
+		Holding {
+			length: ranks.count_ones() as usize,
+			ranks,
+		}
+	}
+
+	fn has(&self, rank: Rank) -> bool {
+		self.ranks & 1 << rank as usize != 0
+	}
+
+	// how many of the ranks given are held
+	fn count(&self, ranks: &[Rank]) -> usize {
+		ranks.iter().filter(|r| self.has(**r)).count()
+	}
+}
+
+impl Hand {
+	// missing aces, kings and queens among the top cards of each suit,
+	// counting no more losers in a suit than it has cards, up to three
+	pub fn losers(&self) -> usize {
+		SUITS
+			.iter()
+			.map(|suit| {
+				let holding = Holding::new(self, *suit);
+				let top = [Rank::Ace, Rank::King, Rank::Queen];
+				let considered = holding.length.min(3);
+				considered - holding.count(&top[..considered])
+			})
+			.sum()
+	}
+
+	// two for each ace and one for each king
+	pub fn controls(&self) -> usize {
+		SUITS.iter().map(|suit| self.suit_controls(*suit)).sum()
+	}
+
+	// the same for one suit, as the constraint language counts it
+	pub fn suit_controls(&self, suit: Suit) -> usize {
+		let holding = Holding::new(self, suit);
+		2 * holding.has(Rank::Ace) as usize + holding.has(Rank::King) as usize
+	}
+
+	pub fn quick_tricks(&self) -> f64 {
+		SUITS
+			.iter()
+			.map(|suit| {
+				let holding = Holding::new(self, *suit);
+				let (ace, king, queen) = (
+					holding.has(Rank::Ace),
+					holding.has(Rank::King),
+					holding.has(Rank::Queen),
+				);
+				match (ace, king, queen) {
+					(true, true, _) => 2.0,
+					(true, false, true) => 1.5,
+					(true, false, false) => 1.0,
+					(false, true, true) => 1.0,
+					(false, true, false) if holding.length > 1 => 0.5,
+					_ => 0.0,
+				}
+			})
+			.sum()
+	}
+
+	// whether the high cards and the two longest suits add up to twenty, for
+	// opening light in first and second seat
+	pub fn rule_of_20(&self) -> bool {
+		let mut lengths = self.distribution().lengths();
+		lengths.sort_unstable();
+		self.hcp() + lengths[3] + lengths[2] >= 20
+	}
+
+	// a point for every card after the fourth in a suit
+	pub fn length_points(&self) -> usize {
+		self.distribution()
+			.lengths()
+			.iter()
+			.map(|len| len.saturating_sub(4))
+			.sum()
+	}
+
+	// three for a void, two for a singleton and one for a doubleton
+	pub fn shortness_points(&self) -> usize {
+		self.distribution()
+			.lengths()
+			.iter()
+			.map(|len| 3usize.saturating_sub(*len))
+			.sum()
+	}
+
+	// high cards, controls, the two longest suits and the difference between
+	// the longest and the shortest
+	pub fn zar_points(&self) -> usize {
+		let mut lengths = self.distribution().lengths();
+		lengths.sort_unstable();
+		self.hcp() + self.controls() + lengths[3] + lengths[2] + lengths[3] - lengths[0]
+	}
+
+	// the Kaplan-Rubens evaluation, on the same scale as the high card count
+	pub fn cccc(&self) -> f64 {
+		self.cccc_hundredths() as f64 / 100.0
+	}
+
+	// the Kaplan-Rubens count in hundredths of a point, as the constraint
+	// language uses it
+	pub(crate) fn cccc_hundredths(&self) -> i64 {
+		let mut eval = 0;
+		for suit in &SUITS {
+			let holding = Holding::new(self, *suit);
+			let length = holding.length;
+			let higher = |ranks: &[Rank]| holding.count(ranks);
+			let (ace, king) = (holding.has(Rank::Ace), holding.has(Rank::King));
+			if ace {
+				eval += 300;
+			}
+			if king {
+				eval += if length == 1 { 50 } else { 200 };
+			}
+			if holding.has(Rank::Queen) {
+				eval += match (length, ace || king) {
+					(1, _) => 0,
+					(2, true) => 50,
+					(2, false) => 25,
+					(_, true) => 100,
+					(_, false) => 75,
+				};
+			}
+			if holding.has(Rank::Jack) {
+				eval += match higher(&[Rank::Ace, Rank::King, Rank::Queen]) {
+					0 => 0,
+					1 => 25,
+					_ => 50,
+				};
+			}
+			if holding.has(Rank::Ten) {
+				let honors = higher(&[Rank::Ace, Rank::King, Rank::Queen, Rank::Jack]);
+				if honors >= 2 || (honors == 1 && holding.has(Rank::Nine)) {
+					eval += 25;
+				}
+			}
+			eval += kr_quality(&holding) * length as i64;
+			eval += match length {
+				0 => 300,
+				1 => 200,
+				2 => 100,
+				_ => 0,
+			};
+		}
+		// the first doubleton is not worth anything, and a flat hand is worth
+		// a little less again
+		eval -= 100;
+		let mut lengths = self.distribution().lengths();
+		lengths.sort_unstable();
+		if lengths == [3, 3, 3, 4] {
+			eval -= 50;
+		}
+		eval
+	}
+
+	// the suit's length plus its honors among the top five cards, which is
+	// enough to overcall or preempt in at ten or more
+	pub fn suit_quality(&self, suit: Suit) -> usize {
+		let holding = Holding::new(self, suit);
+		let honors = [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten];
+		holding.length + holding.count(&honors)
+	}
+
+	// whether the suit is stopped for no trump: the ace, or the king, queen or
+	// jack with one, two or three cards guarding it
+	pub fn stopper(&self, suit: Suit) -> bool {
+		let holding = Holding::new(self, suit);
+		let length = holding.length;
+		holding.has(Rank::Ace)
+			|| (holding.has(Rank::King) && length >= 2)
+			|| (holding.has(Rank::Queen) && length >= 3)
+			|| (holding.has(Rank::Jack) && length >= 4)
+	}
+}
+
+// Kaplan-Rubens suit quality in hundredths of a point for each card in the
+// suit: 40, 30, 20 and 10 for the ace to the jack, with the ten and nine
+// counting when backed by other honors. Long suits need fewer honors, so in
+// suits of seven cards or more a missing queen or jack counts as held, one
+// for each card beyond the sixth.
+fn kr_quality(holding: &Holding) -> i64 {
+	let mut quality = 0;
+	let mut honors = 0;
+	let mut replace = holding.length.saturating_sub(6);
+	for (rank, value) in &[
+		(Rank::Ace, 40),
+		(Rank::King, 30),
+		(Rank::Queen, 20),
+		(Rank::Jack, 10),
+	] {
+		if holding.has(*rank) {
+			quality += value;
+			honors += 1;
+		} else if replace > 0 && (*rank == Rank::Queen || *rank == Rank::Jack) {
+			quality += value;
+			replace -= 1;
+		}
+	}
+	if holding.length >= 7 {
+		return quality;
+	}
+	if holding.has(Rank::Ten) {
+		quality += if honors >= 2 || holding.has(Rank::Jack) {
+			10
+		} else {
+			5
+		};
+	}
+	if holding.has(Rank::Nine)
+		&& (honors == 2 || holding.has(Rank::Ten) || holding.has(Rank::Eight))
+	{
+		quality += 5;
+	}
+	quality
+}
@@ -0,0 +1,133 @@
+use crate::{Hand, Suit};
+
+fn hand(s: &str) -> Hand {
+	Hand::parse_partial(s).unwrap()
+}
+
+macro_rules! test_losers {
+    ($($name:ident: $value:expr,)*) => {
+        mod losers {
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                assert_eq!(super::hand(input).losers(), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_losers! {
+	void: ("AKQJT98|AKQ|432|", 3),
+	singletons: ("A|K|Q|AKQJT9876", 2),
+	doubletons: ("AK|AQ|KQ|AKQJT98", 2),
+	long_suits: ("AQ432|KJ2|Q92|4", 6),
+	yarborough: ("5432|432|432|432", 12),
+}
+
+macro_rules! test_quick_tricks {
+    ($($name:ident: $value:expr,)*) => {
+        mod quick_tricks {
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                assert_eq!(super::hand(input).quick_tricks(), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_quick_tricks! {
+	ace_king: ("AK32|5432|5432|2", 2.0),
+	ace_queen: ("AQ32|5432|5432|2", 1.5),
+	king_queen_and_king: ("KQ32|K432|5432|2", 1.5),
+	singleton_king: ("K|A5432|5432|432", 1.0),
+	nothing: ("QJ32|5432|5432|2", 0.0),
+}
+
+#[test]
+fn controls() {
+	assert_eq!(hand("AK32|K432|A432|2").controls(), 6);
+	assert_eq!(hand("QJ32|QJ32|QJ2|QJ").controls(), 0);
+	assert_eq!(hand("AK32|K432|A432|2").suit_controls(Suit::Spades), 3);
+	assert_eq!(hand("AK32|K432|A432|2").suit_controls(Suit::Clubs), 0);
+}
+
+#[test]
+fn rule_of_20() {
+	// twelve points and two five-card suits
+	assert!(hand("AQ432|KJ432|Q2|2").rule_of_20());
+	assert!(hand("AQ43|KJ43|Q32|32").rule_of_20());
+	assert!(!hand("AJ43|KJ43|Q32|32").rule_of_20());
+}
+
+#[test]
+fn distributional_points() {
+	let long = hand("AQ76432|KJ432||2");
+	assert_eq!(long.length_points(), 4);
+	assert_eq!(long.shortness_points(), 5);
+	let flat = hand("AQ7|KJ4|Q32|5432");
+	assert_eq!(flat.length_points(), 0);
+	assert_eq!(flat.shortness_points(), 0);
+}
+
+#[test]
+fn zar_points() {
+	// 12 hcp, 3 controls, 5 + 5 and 5 - 1
+	assert_eq!(hand("AQ432|KJ432|Q2|2").zar_points(), 12 + 3 + 10 + 4);
+}
+
+#[test]
+fn cccc() {
+	// ace, king and queen in spades with quality (4 + 3 + 2) * 3 / 10, the
+	// jack-ten-nine of hearts, less a point and a half for the flat shape
+	assert_eq!(hand("AKQ|JT9|876|5432").cccc(), 8.2);
+	// a singleton king is worth half a point and shortness counts, less the
+	// first doubleton
+	assert_eq!(
+		hand("K|5432|65432|432").cccc_hundredths(),
+		50 + 30 + 200 - 100
+	);
+	// a seven-card suit missing the queen counts it as held
+	let long = hand("AKJ5432|2|432|32");
+	assert_eq!(
+		long.cccc_hundredths(),
+		300 + 200 + 50 + 700 + 200 + 100 - 100
+	);
+}
+
+#[test]
+fn suit_quality() {
+	let hand = hand("AKJ5432|QT9|Q2|2");
+	assert_eq!(hand.suit_quality(Suit::Spades), 10);
+	assert_eq!(hand.suit_quality(Suit::Hearts), 5);
+	assert_eq!(hand.suit_quality(Suit::Clubs), 1);
+}
+
+macro_rules! test_stopper {
+    ($($name:ident: $value:expr,)*) => {
+        mod stopper {
+            use crate::Suit;
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                assert_eq!(super::hand(input).stopper(Suit::Spades), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_stopper! {
+	ace: ("A|5432|5432|432", true),
+	king_doubleton: ("K2|543|5432|432", true),
+	singleton_king: ("K|5432|5432|432", false),
+	queen_third: ("Q32|543|543|5432", true),
+	queen_doubleton: ("Q2|5432|543|5432", false),
+	jack_fourth: ("J432|543|543|432", true),
+	void: ("|5432|5432|AKQJT", false),
+}
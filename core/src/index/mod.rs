@@ -0,0 +1,128 @@
+//! Deal numbers: every deal of the four hands numbered from zero in
+//! Pavlicek's ordering, which fits in 96 bits, and a short URL-safe form of
+//! the number for sharing deals as links.
+
+use super::{Card, Deal, Deck, Error, Hand, Result, Seat, Vulnerability};
+use std::cmp;
+
+#[cfg(test)]
+mod tests;
+
+// 52! / (13!)^4
+pub const DEALS: u128 = 53_644_737_765_488_792_839_237_440_000;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// sixteen characters of six bits each
+const ID_LENGTH: usize = 16;
+
+// the cards in the order they are assigned, from the ace of spades down to
+// the two of clubs
+fn cards() -> Vec<Card> {
+	let mut cards = Deck::new().0;
+	cards.sort_by_key(|c| cmp::Reverse((c.suit, c.rank)));
+	cards
+}
+
+impl Deal {
+	// the deal's number, which depends only on who holds each card. Every
+	// deal has 13 cards in each hand, so every deal has a number.
+	pub fn index(&self) -> u128 {
+		let holders: Vec<usize> = cards()
+			.into_iter()
+			.map(|card| {
+				Seat::vec()
+					.into_iter()
+					.find(|seat| self.hand_for_seat(*seat).contains(card))
+					.expect("a deal holds every card")
+					.index()
+			})
+			.collect();
+
+		// each card narrows the range of numbers left to the share of deals
+		// in which its holder still has room for it
+		let mut remaining = [13u128; 4];
+		let mut size = DEALS;
+		let mut index = 0;
+		for (left, holder) in (1..=52u128).rev().zip(holders) {
+			for (seat, count) in remaining.iter().enumerate() {
+				let share = size * count / left;
+				if seat == holder {
+					size = share;
+					break;
+				}
+				index += share;
+			}
+			remaining[holder] -= 1;
+		}
+		index
+	}
+
+	pub fn from_index(index: u128, dealer: Seat, vulnerable: Vulnerability) -> Result<Self> {
+		if index >= DEALS {
+			return Err(Error::bridge(format!(
+				"deal numbers must be less than {}",
+				DEALS
+			)));
+		}
+		let mut hands = vec![Vec::new(); 4];
+		let mut remaining = [13u128; 4];
+		let mut size = DEALS;
+		let mut index = index;
+		for (left, card) in (1..=52u128).rev().zip(cards()) {
+			let mut holder = 3;
+			for (seat, count) in remaining.iter().enumerate() {
+				let share = size * count / left;
+				if index < share {
+					holder = seat;
+					size = share;
+					break;
+				}
+				index -= share;
+			}
+			hands[holder].push(card);
+			remaining[holder] -= 1;
+		}
+		let mut hands = hands.into_iter().map(Hand::new);
+		Ok(Deal {
+			dealer,
+			vulnerable,
+			north: hands.next().unwrap(),
+			east: hands.next().unwrap(),
+			south: hands.next().unwrap(),
+			west: hands.next().unwrap(),
+		})
+	}
+
+	// the deal number in sixteen URL-safe base64 characters
+	pub fn id(&self) -> String {
+		let index = self.index();
+		(0..ID_LENGTH)
+			.rev()
+			.map(|i| ALPHABET[(index >> (6 * i)) as usize & 63] as char)
+			.collect()
+	}
+
+	pub fn from_id(id: &str, dealer: Seat, vulnerable: Vulnerability) -> Result<Self> {
+		if id.len() != ID_LENGTH {
+			return Err(Error::parse(format!(
+				"deal ids must be {} characters long",
+				ID_LENGTH
+			)));
+		}
+		let mut index = 0u128;
+		for c in id.bytes() {
+			let digit = match ALPHABET.iter().position(|a| *a == c) {
+				Some(digit) => digit as u128,
+				None => {
+					return Err(Error::parse(format!(
+						"invalid character in deal id: {}",
+						c as char
+					)))
+				}
+			};
+			index = index << 6 | digit;
+		}
+		Self::from_index(index, dealer, vulnerable)
+	}
+}
@@ -0,0 +1,78 @@
+use super::DEALS;
+use crate::{Deal, Hand, Seat, Vulnerability};
+use rand::Rng;
+
+fn deal(north: &str, east: &str, south: &str, west: &str) -> Deal {
+	Deal {
+		dealer: Seat::North,
+		vulnerable: Vulnerability::Neither,
+		north: Hand::parse_partial(north).unwrap(),
+		east: Hand::parse_partial(east).unwrap(),
+		south: Hand::parse_partial(south).unwrap(),
+		west: Hand::parse_partial(west).unwrap(),
+	}
+}
+
+const ALL: &str = "AKQJT98765432";
+
+#[test]
+fn first_and_last_deals() {
+	let first = deal(
+		&format!("{}|||", ALL),
+		&format!("|{}||", ALL),
+		&format!("||{}|", ALL),
+		&format!("|||{}", ALL),
+	);
+	assert_eq!(first.index(), 0);
+	assert_eq!(first.id(), "AAAAAAAAAAAAAAAA");
+
+	let last = deal(
+		&format!("|||{}", ALL),
+		&format!("||{}|", ALL),
+		&format!("|{}||", ALL),
+		&format!("{}|||", ALL),
+	);
+	assert_eq!(last.index(), DEALS - 1);
+	assert_eq!(
+		Deal::from_index(DEALS - 1, Seat::North, Vulnerability::Neither).unwrap(),
+		last
+	);
+}
+
+#[test]
+fn round_trips_random_deals() {
+	let mut rng = rand::thread_rng();
+	for _ in 0..100 {
+		let index = rng.gen_range(0, DEALS);
+		let deal = Deal::from_index(index, Seat::East, Vulnerability::Both).unwrap();
+		for seat in Seat::vec() {
+			assert_eq!(deal.hand_for_seat(seat).0.len(), 13);
+		}
+		assert_eq!(deal.index(), index);
+
+		let id = deal.id();
+		assert_eq!(id.len(), 16);
+		assert_eq!(
+			Deal::from_id(&id, deal.dealer, deal.vulnerable).unwrap(),
+			deal
+		);
+	}
+}
+
+#[test]
+fn consecutive_numbers() {
+	for index in 0..20 {
+		let deal = Deal::from_index(index, Seat::North, Vulnerability::Neither).unwrap();
+		assert_eq!(deal.index(), index);
+	}
+}
+
+#[test]
+fn rejects_invalid_numbers_and_ids() {
+	let deal = Deal::from_index(DEALS, Seat::North, Vulnerability::Neither);
+	assert!(deal.is_err());
+	assert!(Deal::from_id("AAAA", Seat::North, Vulnerability::Neither).is_err());
+	assert!(Deal::from_id("AAAAAAAAAAAAAAA*", Seat::North, Vulnerability::Neither).is_err());
+	// past the last deal
+	assert!(Deal::from_id("________________", Seat::North, Vulnerability::Neither).is_err());
+}
@@ -0,0 +1,216 @@
+//! The suit lengths of all four hands at once, for dealing shape first.
+//!
+//! A random deal gives each seat a row of four suit lengths, and together the
+//! rows hold thirteen cards of each suit. Given the rows each seat may have,
+//! this works out how likely a random deal is to give them all one, and picks
+//! a set of rows with the chance a random deal would have of giving it. The
+//! cards of each suit are then shared out to fit the rows, so a deal made this
+//! way is as likely as it would be from a shuffled deck that happened to fit.
+
+use super::{Card, Deal, Hand, Rank, Seat, Suit, Vulnerability};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+// clubs, diamonds, hearts and spades, as the lengths in a row are held
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+const RANKS: [Rank; 13] = [
+	Rank::Two,
+	Rank::Three,
+	Rank::Four,
+	Rank::Five,
+	Rank::Six,
+	Rank::Seven,
+	Rank::Eight,
+	Rank::Nine,
+	Rank::Ten,
+	Rank::Jack,
+	Rank::Queen,
+	Rank::King,
+	Rank::Ace,
+];
+
+// the suit lengths of one hand, clubs first
+pub(crate) type Row = [usize; 4];
+
+const ROWS: usize = 560;
+
+// every row of thirteen cards
+pub(crate) fn rows() -> Vec<Row> {
+	let mut rows = Vec::new();
+	for clubs in 0..=13 {
+		for diamonds in 0..=13 - clubs {
+			for hearts in 0..=13 - clubs - diamonds {
+				rows.push([clubs, diamonds, hearts, 13 - clubs - diamonds - hearts]);
+			}
+		}
+	}
+	rows
+}
+
+// the binomial coefficients up to 52
+fn binomials() -> Vec<[f64; 53]> {
+	let mut table = vec![[0.0; 53]; 53];
+	for n in 0..53 {
+		table[n][0] = 1.0;
+		for k in 1..=n {
+			table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+		}
+	}
+	table
+}
+
+pub(crate) struct Lengths {
+	// the seats in the order their rows are chosen, those allowed fewer rows
+	// first, and the rows each may have
+	seats: [usize; 4],
+	allowed: [Vec<Row>; 4],
+	// how many seats come before those allowed every row
+	restricted: usize,
+	probability: f64,
+	choose: Vec<[f64; 53]>,
+	// for a seat's place in the order and the suit lengths left when it is
+	// reached, the chance that it and the seats after it get allowed rows
+	chance: HashMap<(usize, Row), f64>,
+}
+
+impl Lengths {
+	// the rows north, east, south and west may have
+	pub(crate) fn new(allowed: [Vec<Row>; 4]) -> Self {
+		let mut seats = [0, 1, 2, 3];
+		seats.sort_by_key(|seat| allowed[*seat].len());
+		let restricted = seats
+			.iter()
+			.filter(|seat| allowed[**seat].len() < ROWS)
+			.count();
+		let mut lengths = Lengths {
+			seats,
+			allowed: seats.map(|seat| allowed[seat].clone()),
+			restricted,
+			probability: 0.0,
+			choose: binomials(),
+			chance: HashMap::new(),
+		};
+		lengths.probability = lengths.chance_from(0, [13; 4]);
+		lengths
+	}
+
+	fn chance_from(&mut self, place: usize, left: Row) -> f64 {
+		// the cards left always give seats allowed every row one
+		if place >= self.restricted {
+			return 1.0;
+		}
+		if let Some(chance) = self.chance.get(&(place, left)) {
+			return *chance;
+		}
+		let mut chance = 0.0;
+		for i in 0..self.allowed[place].len() {
+			let row = self.allowed[place][i];
+			if let Some(rest) = subtract(&left, &row) {
+				let draw = self.draw_chance(&left, &row);
+				if draw > 0.0 {
+					chance += draw * self.chance_from(place + 1, rest);
+				}
+			}
+		}
+		self.chance.insert((place, left), chance);
+		chance
+	}
+
+	// the chance that thirteen cards drawn from the suits left give the row
+	fn draw_chance(&self, left: &Row, row: &Row) -> f64 {
+		let total: usize = left.iter().sum();
+		let ways: f64 = left
+			.iter()
+			.zip(row)
+			.map(|(n, k)| self.choose[*n][*k])
+			.product();
+		ways / self.choose[total][13]
+	}
+
+	// the chance that a random deal gives every seat an allowed row
+	pub(crate) fn probability(&self) -> f64 {
+		self.probability
+	}
+
+	// the rows of north, east, south and west for a random deal that gives
+	// every seat an allowed one, or none if no deal does
+	pub(crate) fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<[Row; 4]> {
+		if self.probability() <= 0.0 {
+			return None;
+		}
+		let mut rows = [[0; 4]; 4];
+		let mut left = [13; 4];
+		for place in 0..4 {
+			let options: Vec<(Row, f64)> = self.allowed[place]
+				.iter()
+				.filter_map(|row| {
+					let rest = subtract(&left, row)?;
+					let next = match place + 1 >= self.restricted {
+						true => 1.0,
+						false => *self.chance.get(&(place + 1, rest))?,
+					};
+					Some((*row, self.draw_chance(&left, row) * next))
+				})
+				.filter(|(_, weight)| *weight > 0.0)
+				.collect();
+			let total: f64 = options.iter().map(|(_, weight)| weight).sum();
+			let mut pick = rng.gen::<f64>() * total;
+			let mut chosen = options.last()?.0;
+			for (row, weight) in &options {
+				if pick < *weight {
+					chosen = *row;
+					break;
+				}
+				pick -= weight;
+			}
+			rows[self.seats[place]] = chosen;
+			left = subtract(&left, &chosen)?;
+		}
+		Some(rows)
+	}
+}
+
+fn subtract(left: &Row, row: &Row) -> Option<Row> {
+	let mut rest = [0; 4];
+	for i in 0..4 {
+		rest[i] = left[i].checked_sub(row[i])?;
+	}
+	Some(rest)
+}
+
+// deals each suit at random to fit the rows of north, east, south and west
+pub(crate) fn deal<R: Rng + ?Sized>(
+	rows: &[Row; 4],
+	dealer: Seat,
+	vulnerable: Vulnerability,
+	rng: &mut R,
+) -> Deal {
+	let mut hands: Vec<Vec<Card>> = vec![Vec::new(); 4];
+	for (i, suit) in SUITS.iter().enumerate() {
+		let mut cards: Vec<Card> = RANKS
+			.iter()
+			.map(|rank| Card {
+				rank: *rank,
+				suit: *suit,
+			})
+			.collect();
+		cards.shuffle(rng);
+		let mut cards = cards.into_iter();
+		for (hand, row) in hands.iter_mut().zip(rows) {
+			hand.extend(cards.by_ref().take(row[i]));
+		}
+	}
+	let mut hands = hands.into_iter().map(Hand::new);
+	Deal {
+		dealer,
+		vulnerable,
+		north: hands.next().unwrap(),
+		east: hands.next().unwrap(),
+		south: hands.next().unwrap(),
+		west: hands.next().unwrap(),
+	}
+}
@@ -0,0 +1,60 @@
+use super::{deal, rows, Lengths, Row};
+use crate::{seeded_rng, Seat, Suit, Vulnerability};
+
+fn all() -> [Vec<Row>; 4] {
+	[rows(), rows(), rows(), rows()]
+}
+
+#[test]
+fn lists_every_row() {
+	let rows = rows();
+	assert_eq!(rows.len(), 560);
+	assert!(rows.iter().all(|row| row.iter().sum::<usize>() == 13));
+}
+
+#[test]
+fn every_deal_has_some_rows() {
+	let lengths = Lengths::new(all());
+	assert!((lengths.probability() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn knows_how_likely_a_shape_is() {
+	// north 7-4 in the majors, spades the longer
+	let mut allowed = all();
+	allowed[0] = vec![[1, 1, 4, 7], [2, 0, 4, 7], [0, 2, 4, 7]];
+	let lengths = Lengths::new(allowed);
+	// 13C7 * 13C4 * 26C2 / 52C13
+	let expected = 1716.0 * 715.0 * 325.0 / 635013559600.0;
+	assert!((lengths.probability() - expected).abs() < 1e-12);
+}
+
+#[test]
+fn samples_only_allowed_rows() {
+	let mut allowed = all();
+	allowed[2] = vec![[0, 0, 0, 13]];
+	allowed[3] = rows().into_iter().filter(|row| row[3] == 0).collect();
+	let lengths = Lengths::new(allowed);
+	let mut rng = seeded_rng(4);
+	for _ in 0..20 {
+		let rows = lengths.sample(&mut rng).unwrap();
+		assert_eq!(rows[2], [0, 0, 0, 13]);
+		for suit in 0..4 {
+			assert_eq!(rows.iter().map(|row| row[suit]).sum::<usize>(), 13);
+		}
+		let deal = deal(&rows, Seat::North, Vulnerability::Both, &mut rng);
+		assert!(deal.check().is_ok());
+		assert_eq!(deal.south.suit_holding(Suit::Spades).len(), 13);
+	}
+}
+
+#[test]
+fn impossible_rows_give_nothing() {
+	// two seats cannot both hold seven spades
+	let mut allowed = all();
+	allowed[0] = rows().into_iter().filter(|row| row[3] >= 7).collect();
+	allowed[1] = rows().into_iter().filter(|row| row[3] >= 7).collect();
+	let lengths = Lengths::new(allowed);
+	assert_eq!(lengths.probability(), 0.0);
+	assert!(lengths.sample(&mut seeded_rng(1)).is_none());
+}
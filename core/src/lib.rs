@@ -1,6 +1,30 @@
-use rand::{distributions::{Distribution, Standard}, Rng};
+use rand::{distributions::{Distribution, Standard}, Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use std::{cmp, fmt};
+use self::builder::DealBuilder;
+use self::constraint::{Constraint, Generator};
+use self::cardset::CardSet;
+use self::scoring::{Doubling, Score};
+use std::{cmp, fmt, io};
+
+pub mod auction;
+pub mod board;
+pub mod builder;
+pub mod cardset;
+pub mod constraint;
+pub mod dd;
+pub mod eval;
+pub mod index;
+mod lengths;
+pub mod lin;
+pub mod notation;
+pub mod par;
+pub mod pbn;
+pub mod play;
+pub mod render;
+pub mod scoring;
+pub mod shape;
+pub mod sim;
 
 #[cfg(test)]
 mod tests;
@@ -9,6 +33,8 @@ mod tests;
 pub enum Error {
 	Bridge(String),
 	Parse(String),
+	Io(io::Error),
+	Deal(DealError),
 }
 
 impl Error {
@@ -21,6 +47,56 @@ impl Error {
 	}
 }
 
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Bridge(msg) | Error::Parse(msg) => write!(f, "{}", msg),
+			Error::Io(e) => write!(f, "{}", e),
+			Error::Deal(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+impl From<DealError> for Error {
+	fn from(e: DealError) -> Self {
+		Error::Deal(e)
+	}
+}
+
+// why a set of hands is not a deal
+#[derive(Debug, Clone, PartialEq)]
+pub enum DealError {
+	// a hand cannot hold more than 13 cards
+	TooManyCards(usize),
+	// nor, once it is complete, fewer
+	TooFewCards(usize),
+	// each seat of a deal holds exactly 13 cards
+	HandSize(Seat, usize),
+	DuplicateCard(Card),
+	MissingHand(Seat),
+	// the cards asked for cannot all be dealt at once
+	Impossible,
+}
+
+impl fmt::Display for DealError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DealError::TooManyCards(n) => write!(f, "a hand cannot hold {} cards", n),
+			DealError::TooFewCards(n) => write!(f, "a hand holds 13 cards, not {}", n),
+			DealError::HandSize(seat, n) => write!(f, "{} holds {} cards, not 13", seat, n),
+			DealError::DuplicateCard(card) => write!(f, "{} is dealt twice", card),
+			DealError::MissingHand(seat) => write!(f, "{} has no hand", seat),
+			DealError::Impossible => write!(f, "no deal holds every card asked for"),
+		}
+	}
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -28,14 +104,7 @@ pub struct Contract(Level, Trump);
 
 impl Contract {
 	pub fn parse(s: &str) -> Result<Self> {
-		if s.len() < 2 {
-			return Err(Error::parse(
-				"contract string must have length of at least 2",
-			));
-		}
-		let level = Level::parse(&s[0..1])?;
-		let trump = Trump::parse(&s[1..])?;
-		Ok(Contract(level, trump))
+		notation::english().parse_contract(s)
 	}
 }
 
@@ -45,13 +114,19 @@ impl fmt::Display for Contract {
 	}
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
 pub struct Card {
 	pub rank: Rank,
 	pub suit: Suit,
 }
 
-#[derive(Serialize, Deserialize)]
+impl fmt::Display for Card {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}{}", self.suit, self.rank)
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Vulnerability {
 	NS,
 	EW,
@@ -73,6 +148,15 @@ impl Vulnerability {
 			))),
 		}
 	}
+
+	pub fn is_vulnerable(self, side: Side) -> bool {
+		matches!(
+			(self, side),
+			(Vulnerability::Both, _)
+				| (Vulnerability::NS, Side::NS)
+				| (Vulnerability::EW, Side::EW)
+		)
+	}
 }
 
 impl fmt::Display for Vulnerability {
@@ -100,7 +184,7 @@ impl Distribution<Vulnerability> for Standard {
 	}
 }
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Seat {
 	North,
 	East,
@@ -113,6 +197,41 @@ impl Seat {
 		vec![Seat::North, Seat::East, Seat::South, Seat::West]
 	}
 
+	// north, east, south and west are 0 to 3, for arrays kept by seat
+	pub fn index(self) -> usize {
+		match self {
+			Seat::North => 0,
+			Seat::East => 1,
+			Seat::South => 2,
+			Seat::West => 3,
+		}
+	}
+
+	// the seat that many turns clockwise from this one
+	fn after(self, turns: usize) -> Seat {
+		Seat::vec()[(self.index() + turns) % 4]
+	}
+
+	// the seat to the left, which plays or calls next
+	pub fn lho(self) -> Seat {
+		self.after(1)
+	}
+
+	pub fn partner(self) -> Seat {
+		self.after(2)
+	}
+
+	pub fn rho(self) -> Seat {
+		self.after(3)
+	}
+
+	pub fn side(self) -> Side {
+		match self {
+			Seat::North | Seat::South => Side::NS,
+			Seat::East | Seat::West => Side::EW,
+		}
+	}
+
 	pub fn parse(s: &str) -> Result<Seat> {
 		use self::Seat::*;
 		match s {
@@ -138,6 +257,39 @@ impl fmt::Display for Seat {
 	}
 }
 
+// a partnership
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+	NS,
+	EW,
+}
+
+impl Side {
+	pub fn seats(self) -> [Seat; 2] {
+		match self {
+			Side::NS => [Seat::North, Seat::South],
+			Side::EW => [Seat::East, Seat::West],
+		}
+	}
+
+	pub fn opponents(self) -> Side {
+		match self {
+			Side::NS => Side::EW,
+			Side::EW => Side::NS,
+		}
+	}
+}
+
+impl fmt::Display for Side {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let s = match self {
+			Side::NS => "NS",
+			Side::EW => "EW",
+		};
+		write!(f, "{}", s)
+	}
+}
+
 impl Distribution<Seat> for Standard {
 	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Seat {
 		match rng.gen_range(0, 4) {
@@ -150,8 +302,36 @@ impl Distribution<Seat> for Standard {
 	}
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BidSequence(Vec<Bid>);
+// an alert or an explanation of a call, such as for an artificial bid
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Annotation {
+	pub alert: bool,
+	pub explanation: Option<String>,
+}
+
+impl Annotation {
+	pub fn alert() -> Self {
+		Annotation {
+			alert: true,
+			explanation: None,
+		}
+	}
+
+	pub fn explained<T: Into<String>>(alert: bool, explanation: T) -> Self {
+		Annotation {
+			alert,
+			explanation: Some(explanation.into()),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		!self.alert && self.explanation.is_none()
+	}
+}
+
+// the calls of an auction, each with its annotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct BidSequence(Vec<Bid>, Vec<Annotation>);
 
 impl BidSequence {
 	pub fn empty() -> Self {
@@ -159,36 +339,57 @@ impl BidSequence {
 	}
 
 	pub fn new(bids: Vec<Bid>) -> Self {
-		BidSequence(bids)
+		let annotations = vec![Annotation::default(); bids.len()];
+		BidSequence(bids, annotations)
 	}
 
 	pub fn bids(&self) -> &[Bid] {
 		&self.0
 	}
 
+	pub fn annotations(&self) -> &[Annotation] {
+		&self.1
+	}
+
+	pub fn annotation(&self, call: usize) -> Option<&Annotation> {
+		self.1.get(call)
+	}
+
+	pub fn annotate(&mut self, call: usize, annotation: Annotation) -> Result<()> {
+		match self.1.get_mut(call) {
+			Some(a) => {
+				*a = annotation;
+				Ok(())
+			}
+			None => Err(Error::bridge(format!("there is no call {} to annotate", call))),
+		}
+	}
+
+	// the call as written in an auction, with a `!` if it is alerted
+	fn call_string(&self, call: usize) -> String {
+		let alert = if self.1[call].alert { "!" } else { "" };
+		format!("{}{}", self.0[call], alert)
+	}
+
 	pub fn next_seat(&self, dealer: Seat) -> Seat {
-		let offset = match dealer {
-			Seat::North => 0,
-			Seat::East => 1,
-			Seat::South => 2,
-			Seat::West => 3,
-		};
-		Seat::vec()
-			.into_iter()
-			.cycle()
-			.nth(self.0.len() + offset)
-			.unwrap()
+		dealer.after(self.0.len())
 	}
 
 	pub fn parse(s: &str) -> Result<Self> {
 		if s.len() == 0 {
 			return Ok(Self::empty());
 		}
-		let bids: Result<Vec<Bid>> = s.split(",").map(Bid::parse).collect();
-		match bids {
-			Ok(vec) => Ok(BidSequence(vec)),
-			Err(e) => Err(e),
+		let mut bids = Vec::new();
+		let mut annotations = Vec::new();
+		for call in s.split(",") {
+			let alert = call.ends_with('!');
+			bids.push(Bid::parse(call.trim_end_matches('!'))?);
+			annotations.push(Annotation {
+				alert,
+				explanation: None,
+			});
 		}
+		Ok(BidSequence(bids, annotations))
 	}
 
 	pub fn is_finished(&self) -> bool {
@@ -228,6 +429,40 @@ impl BidSequence {
 		None
 	}
 
+	// the result of a finished auction: the last contract bid, doubled or
+	// redoubled if that was the last call after it, played by the player of
+	// the declaring side who first named its strain
+	pub fn final_contract(&self, dealer: Seat) -> Result<AuctionResult> {
+		if !self.is_finished() {
+			return Err(Error::bridge("the auction is not finished"));
+		}
+		let (last, contract) = match self.last_contract_bid() {
+			Some((idx, Bid::Contract(contract))) => (idx, contract),
+			_ => return Ok(AuctionResult::PassedOut),
+		};
+		let doubling = match self.last_non_pass_bid() {
+			Some((_, Bid::Double)) => Doubling::Doubled,
+			Some((_, Bid::Redouble)) => Doubling::Redoubled,
+			_ => Doubling::Undoubled,
+		};
+		let first = self
+			.0
+			.iter()
+			.enumerate()
+			.filter(|(idx, _)| idx % 2 == last % 2)
+			.position(|(_, bid)| match bid {
+				Bid::Contract(c) => c.1 == contract.1,
+				_ => false,
+			})
+			.unwrap();
+		let declarer = dealer.after(last % 2 + 2 * first);
+		Ok(AuctionResult::Contract(FinalContract {
+			contract,
+			doubling,
+			declarer,
+		}))
+	}
+
 	pub fn validate_continuation(&self, next: Bid) -> Result<()> {
 		if self.valid_continuation(next) {
 			Ok(())
@@ -269,16 +504,26 @@ impl BidSequence {
 	}
 
 	pub fn with_continuation(&self, next: Bid) -> Result<BidSequence> {
+		self.with_annotated_continuation(next, Annotation::default())
+	}
+
+	pub fn with_annotated_continuation(
+		&self,
+		next: Bid,
+		annotation: Annotation,
+	) -> Result<BidSequence> {
 		if self.valid_continuation(next) {
 			let mut new_seq = self.0.clone();
 			new_seq.push(next.clone());
-			Ok(BidSequence(new_seq))
+			let mut annotations = self.1.clone();
+			annotations.push(annotation);
+			Ok(BidSequence(new_seq, annotations))
 		} else {
 			Err(Error::bridge("invalid continuation of bid sequence"))
 		}
 	}
 
-	fn pad_for_table(&self, dealer: Seat) -> Vec<Option<Bid>> {
+	fn pad_for_table(&self, dealer: Seat) -> Vec<Option<String>> {
 		// North is first seat shown on table, so we need to add some empty bids
 		// if North is not the dealer
 		let num_empty = match dealer {
@@ -291,8 +536,8 @@ impl BidSequence {
 		for _ in 0..num_empty {
 			padded.push(None)
 		}
-		for bid in &self.0 {
-			padded.push(Some(bid.clone()))
+		for call in 0..self.0.len() {
+			padded.push(Some(self.call_string(call)))
 		}
 		while padded.len() % 4 != 0 || padded.len() == 0 {
 			padded.push(None)
@@ -306,11 +551,7 @@ impl BidSequence {
 		let bid_strings: Vec<String> = self
 			.pad_for_table(dealer)
 			.iter()
-			.map(|bid_opt| {
-				bid_opt
-					.clone()
-					.map_or("".to_string(), |bid| format!("{}", bid))
-			})
+			.map(|call| call.clone().unwrap_or_default())
 			.collect();
 
 		// print table header
@@ -327,22 +568,132 @@ impl BidSequence {
 			)?;
 			writeln!(f, "+-----+-----+-----+-----+")?;
 		}
+
+		// explanations follow the table
+		for (call, annotation) in self.1.iter().enumerate() {
+			if let Some(explanation) = &annotation.explanation {
+				let seat = dealer.after(call);
+				writeln!(f, "{} {}: {}", seat, self.call_string(call), explanation)?;
+			}
+		}
 		Ok(())
 	}
 }
 
-impl fmt::Display for BidSequence {
-	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		let s = self
+// a call as stored, written as just the bid when it has no annotation
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredCall {
+	Bid(Bid),
+	Annotated {
+		bid: Bid,
+		#[serde(default)]
+		alert: bool,
+		#[serde(default)]
+		explanation: Option<String>,
+	},
+}
+
+impl Serialize for BidSequence {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let calls: Vec<StoredCall> = self
 			.0
 			.iter()
-			.map(|b| format!("{}", b))
+			.zip(&self.1)
+			.map(|(bid, annotation)| {
+				if annotation.is_empty() {
+					StoredCall::Bid(*bid)
+				} else {
+					StoredCall::Annotated {
+						bid: *bid,
+						alert: annotation.alert,
+						explanation: annotation.explanation.clone(),
+					}
+				}
+			})
+			.collect();
+		calls.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for BidSequence {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let calls = Vec::<StoredCall>::deserialize(deserializer)?;
+		let (bids, annotations) = calls
+			.into_iter()
+			.map(|call| match call {
+				StoredCall::Bid(bid) => (bid, Annotation::default()),
+				StoredCall::Annotated {
+					bid,
+					alert,
+					explanation,
+				} => (bid, Annotation { alert, explanation }),
+			})
+			.unzip();
+		Ok(BidSequence(bids, annotations))
+	}
+}
+
+impl fmt::Display for BidSequence {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let s = (0..self.0.len())
+			.map(|call| self.call_string(call))
 			.collect::<Vec<String>>()
 			.join(",");
 		write!(f, "{}", s)
 	}
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AuctionResult {
+	PassedOut,
+	Contract(FinalContract),
+}
+
+impl fmt::Display for AuctionResult {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			AuctionResult::PassedOut => write!(f, "passed out"),
+			AuctionResult::Contract(contract) => write!(f, "{}", contract),
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinalContract {
+	pub contract: Contract,
+	pub doubling: Doubling,
+	pub declarer: Seat,
+}
+
+impl FinalContract {
+	pub fn score(&self, tricks: usize, vulnerable: Vulnerability) -> Score {
+		Score::new(
+			self.contract,
+			self.doubling,
+			self.declarer,
+			tricks,
+			vulnerable,
+		)
+	}
+}
+
+impl fmt::Display for FinalContract {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{}{} by {}",
+			self.contract, self.doubling, self.declarer
+		)
+	}
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
 pub enum Bid {
 	Pass,
@@ -353,13 +704,7 @@ pub enum Bid {
 
 impl Bid {
 	pub fn parse(s: &str) -> Result<Self> {
-		let bid = match s {
-			"Pass" | "P" => Bid::Pass,
-			"Dbl" => Bid::Double,
-			"Rdbl" => Bid::Redouble,
-			_ => Bid::Contract(Contract::parse(s)?),
-		};
-		Ok(bid)
+		notation::english().parse_bid(s)
 	}
 }
 
@@ -425,15 +770,8 @@ pub enum Suit {
 }
 
 impl Suit {
-	fn parse(s: &str) -> Result<Self> {
-		use self::Suit::*;
-		match s {
-			"S" => Ok(Spades),
-			"H" => Ok(Hearts),
-			"D" => Ok(Diamonds),
-			"C" => Ok(Clubs),
-			_ => Err(Error::parse(format!("failed to parse suit string '{}'", s))),
-		}
+	pub fn parse(s: &str) -> Result<Self> {
+		notation::english().parse_suit(s)
 	}
 }
 
@@ -458,11 +796,7 @@ pub enum Trump {
 
 impl Trump {
 	pub fn parse(s: &str) -> Result<Trump> {
-		use self::Trump::*;
-		match s {
-			"NT" => Ok(NoTrump),
-			_ => Ok(Trump(Suit::parse(s)?)),
-		}
+		notation::english().parse_trump(s)
 	}
 }
 
@@ -505,6 +839,18 @@ impl Level {
 			))),
 		}
 	}
+
+	pub fn number(self) -> usize {
+		match self {
+			Level::One => 1,
+			Level::Two => 2,
+			Level::Three => 3,
+			Level::Four => 4,
+			Level::Five => 5,
+			Level::Six => 6,
+			Level::Seven => 7,
+		}
+	}
 }
 
 impl fmt::Display for Level {
@@ -523,7 +869,7 @@ impl fmt::Display for Level {
 	}
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
 pub enum Rank {
 	Two,
 	Three,
@@ -542,23 +888,7 @@ pub enum Rank {
 
 impl Rank {
 	pub fn parse(s: &str) -> Result<Rank> {
-		use self::Rank::*;
-		match s {
-			"A" => Ok(Ace),
-			"K" => Ok(King),
-			"Q" => Ok(Queen),
-			"J" => Ok(Jack),
-			"T" => Ok(Ten),
-			"9" => Ok(Nine),
-			"8" => Ok(Eight),
-			"7" => Ok(Seven),
-			"6" => Ok(Six),
-			"5" => Ok(Five),
-			"4" => Ok(Four),
-			"3" => Ok(Three),
-			"2" => Ok(Two),
-			_ => Err(Error::parse(format!("invalid rank string '{}'", s))),
-		}
+		notation::english().parse_rank(s)
 	}
 
 	fn hcp(&self) -> usize {
@@ -630,9 +960,9 @@ impl Deck {
 		let cards = self.0;
 		(
 			Hand::new(cards[0..13].to_owned()),
-			Hand::new(cards[14..26].to_owned()),
-			Hand::new(cards[27..39].to_owned()),
-			Hand::new(cards[40..52].to_owned()),
+			Hand::new(cards[13..26].to_owned()),
+			Hand::new(cards[26..39].to_owned()),
+			Hand::new(cards[39..52].to_owned()),
 		)
 	}
 }
@@ -646,30 +976,20 @@ impl Distribution<Deck> for Standard {
 	}
 }
 
-pub struct SuitCards(Vec<Card>);
+// the cards of one suit, from the highest down
+pub struct SuitCards(CardSet);
 
 impl SuitCards {
-	fn empty() -> SuitCards {
-		Self::new(Vec::new())
+	pub fn len(&self) -> usize {
+		self.0.len()
 	}
 
-	fn new(cards: Vec<Card>) -> SuitCards {
-		SuitCards(cards)
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
 	}
 
-	fn parse(suit: Suit, ranks: &str) -> Result<SuitCards> {
-		if ranks.len() == 0 {
-			return Ok(SuitCards::empty());
-		}
-		let cards: Result<Vec<Card>> = ranks
-			.chars()
-			.map(|c| c.to_string())
-			.map(|rank_string| match Rank::parse(&rank_string) {
-				Ok(rank) => Ok(Card { rank, suit }),
-				Err(e) => Err(e),
-			})
-			.collect();
-		Ok(Self::new(cards?))
+	pub fn cards(&self) -> CardSet {
+		self.0
 	}
 }
 
@@ -680,47 +1000,108 @@ impl fmt::Display for SuitCards {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct Hand(Vec<Card>);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand(CardSet);
 
 impl Hand {
-	pub fn new(cards: Vec<Card>) -> Hand {
-		let mut cards = cards.clone();
-		cards.sort();
-		cards.reverse();
+	// unchecked, so only for cards the crate has dealt or checked itself;
+	// hands from outside come through `checked` or `partial`
+	pub(crate) fn new(cards: Vec<Card>) -> Hand {
+		Hand(cards.into_iter().collect())
+	}
+
+	// a complete hand of 13 cards, none of them given twice
+	pub fn checked(cards: Vec<Card>) -> Result<Hand> {
+		Ok(Hand(Self::check(&cards, true)?))
+	}
+
+	// part of a hand, as given to `DealBuilder` or left in an ending: no card
+	// twice and at most 13
+	pub fn partial(cards: Vec<Card>) -> Result<Hand> {
+		Ok(Hand(Self::check(&cards, false)?))
+	}
+
+	fn check(cards: &[Card], complete: bool) -> std::result::Result<CardSet, DealError> {
+		let mut set = CardSet::empty();
+		for card in cards {
+			if !set.insert(*card) {
+				return Err(DealError::DuplicateCard(*card));
+			}
+		}
+		if set.len() > 13 {
+			return Err(DealError::TooManyCards(set.len()));
+		}
+		if complete && set.len() < 13 {
+			return Err(DealError::TooFewCards(set.len()));
+		}
+		Ok(set)
+	}
+
+	pub(crate) fn from_cards(cards: CardSet) -> Hand {
 		Hand(cards)
 	}
 
+	// a complete hand, as written in `Notation::parse_hand`
 	pub fn parse(s: &str) -> Result<Hand> {
-		let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
-		let suit_cards: Result<Vec<Vec<Card>>> = suits
-			.iter()
-			.zip(s.split("|"))
-			.map(|(suit, ranks)| match SuitCards::parse(*suit, ranks) {
-				Ok(suit_cards) => Ok(suit_cards.0),
-				Err(e) => Err(e),
-			})
-			.collect();
-		let cards: Vec<Card> = suit_cards?.into_iter().flatten().collect();
-		Ok(Hand::new(cards))
+		notation::english().parse_hand(s)
+	}
+
+	// part of a hand, written the same way
+	pub fn parse_partial(s: &str) -> Result<Hand> {
+		notation::english().parse_partial_hand(s)
+	}
+
+	pub fn cards(&self) -> CardSet {
+		self.0
+	}
+
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub fn contains(&self, card: Card) -> bool {
+		self.0.contains(card)
 	}
 
 	pub fn suit_holding(&self, suit: Suit) -> SuitCards {
-		let cards = self
-			.0
-			.clone()
-			.into_iter()
-			.filter(|c| c.suit == suit)
-			.collect();
-		SuitCards(cards)
+		SuitCards(self.0.suit(suit))
 	}
 
 	pub fn hcp(&self) -> usize {
-		self.0.iter().fold(0, |total, card| total + card.rank.hcp())
+		self.0.hcp()
 	}
 
 	pub fn distribution(&self) -> Dist {
-		self.0.iter().fold(Dist::empty(), |dist, card| dist.inc(card.suit))
+		Dist {
+			spades: self.0.suit_length(Suit::Spades),
+			hearts: self.0.suit_length(Suit::Hearts),
+			diamonds: self.0.suit_length(Suit::Diamonds),
+			clubs: self.0.suit_length(Suit::Clubs),
+		}
+	}
+}
+
+// stored as the list of cards, as hands were before they were card sets
+impl Serialize for Hand {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_seq(self.0.iter())
+	}
+}
+
+impl<'de> Deserialize<'de> for Hand {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<Hand, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let cards = Vec::<Card>::deserialize(deserializer)?;
+		Hand::check(&cards, true).map(Hand).map_err(de::Error::custom)
 	}
 }
 
@@ -733,6 +1114,7 @@ impl fmt::Display for Hand {
 	}
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Dist {
 	pub spades: usize,
 	pub hearts: usize,
@@ -741,24 +1123,6 @@ pub struct Dist {
 }
 
 impl Dist {
-	fn empty() -> Self {
-		Dist {
-			spades: 0,
-			hearts: 0,
-			diamonds: 0,
-			clubs: 0,
-		}
-	}
-
-	fn inc(self, suit: Suit) -> Self {
-		match suit {
-			Suit::Spades => Dist{spades: self.spades+1, ..self},
-			Suit::Hearts => Dist{hearts: self.hearts+1, ..self},
-			Suit::Diamonds => Dist{diamonds: self.diamonds+1, ..self},
-			Suit::Clubs => Dist{clubs: self.clubs+1, ..self},
-		}
-	}
-
 	pub fn is_balanced(&self) -> bool {
 		let suits = [self.spades, self.hearts, self.diamonds, self.clubs];
 		let (min_len, num_shorter_than_three) = suits.iter().fold((13, 0), |acc, suit| {
@@ -773,34 +1137,76 @@ impl Dist {
 	}
 }
 
-#[derive(Serialize, Deserialize)]
+// the generator behind every seeded constructor, so a seed names the same
+// deal wherever it is used. The seed fills the start of a ChaCha20 key, and
+// neither step depends on the version of rand or the platform.
+pub fn seeded_rng(seed: u64) -> ChaChaRng {
+	let mut key = [0; 32];
+	key[..8].copy_from_slice(&seed.to_le_bytes());
+	ChaChaRng::from_seed(key)
+}
+
+const FIRST_SEAT_ONE_NT_OPENER: &str = "balanced(dealer) && hcp(dealer) >= 15 && hcp(dealer) <= 17";
+
+// four hands of 13 cards between them holding the whole deck. Deals from
+// outside the crate come through `DealBuilder` or a parser, which check this.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Deal {
 	pub dealer: Seat,
 	pub vulnerable: Vulnerability,
-	pub north: Hand,
-	pub east: Hand,
-	pub south: Hand,
-	pub west: Hand,
+	north: Hand,
+	east: Hand,
+	south: Hand,
+	west: Hand,
 }
 
 impl Deal {
-	pub fn random() -> Self {
-		rand::random()
+	pub fn builder() -> DealBuilder {
+		DealBuilder::new()
 	}
 
-	pub fn first_seat_one_nt_opener() -> Self {
-		loop {
-			let mut deal: Deal = rand::random();
-			for seat in Seat::vec() {
-				let hand = deal.hand_for_seat(seat);
-				let balanced = hand.distribution().is_balanced();
-				let hcp = hand.hcp();
-				if balanced && hcp >= 15 && hcp <= 17 {
-					deal.dealer = seat;
-					return deal;
-				} 
+	// the first thing that stops the hands making a deal, if any
+	fn check(&self) -> std::result::Result<(), DealError> {
+		let mut dealt = CardSet::empty();
+		for seat in Seat::vec() {
+			let cards = self.hand_for_seat(seat).cards();
+			if cards.len() != 13 {
+				return Err(DealError::HandSize(seat, cards.len()));
+			}
+			if let Some(card) = (dealt & cards).iter().next() {
+				return Err(DealError::DuplicateCard(card));
 			}
+			dealt |= cards;
 		}
+		Ok(())
+	}
+
+	pub fn random() -> Self {
+		Self::random_with_rng(&mut rand::thread_rng())
+	}
+
+	pub fn random_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		rng.gen()
+	}
+
+	// the same seed always gives the same deal
+	pub fn from_seed(seed: u64) -> Self {
+		Self::random_with_rng(&mut seeded_rng(seed))
+	}
+
+	pub fn first_seat_one_nt_opener() -> Self {
+		Self::first_seat_one_nt_opener_with_rng(&mut rand::thread_rng())
+	}
+
+	pub fn first_seat_one_nt_opener_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		let constraint = Constraint::parse(FIRST_SEAT_ONE_NT_OPENER).unwrap();
+		Generator::new(constraint)
+			.generate_with_rng(rng)
+			.expect("a 1NT opener should turn up well within the attempt limit")
+	}
+
+	pub fn first_seat_one_nt_opener_from_seed(seed: u64) -> Self {
+		Self::first_seat_one_nt_opener_with_rng(&mut seeded_rng(seed))
 	}
 
 	pub fn hand_for_seat(&self, seat: Seat) -> &Hand {
@@ -818,14 +1224,72 @@ impl Distribution<Deal> for Standard {
 		let dealer: Seat = self.sample(rng);
 		let vulnerable: Vulnerability = self.sample(rng);
 		let deck: Deck = self.sample(rng);
-		let (north, south, east, west) = deck.deal();
+		let (north, east, south, west) = deck.deal();
 		Deal {
 			dealer,
 			vulnerable,
 			north,
-			south,
 			east,
+			south,
 			west,
 		}
 	}
 }
+
+impl<'de> Deserialize<'de> for Deal {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<Deal, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		#[derive(Deserialize)]
+		struct Fields {
+			dealer: Seat,
+			vulnerable: Vulnerability,
+			north: Hand,
+			east: Hand,
+			south: Hand,
+			west: Hand,
+		}
+
+		let fields = Fields::deserialize(deserializer)?;
+		let deal = Deal {
+			dealer: fields.dealer,
+			vulnerable: fields.vulnerable,
+			north: fields.north,
+			east: fields.east,
+			south: fields.south,
+			west: fields.west,
+		};
+		deal.check().map_err(de::Error::custom)?;
+		Ok(deal)
+	}
+}
+
+// a deal as stored before every hand was dealt 13 cards, when `Deck::deal`
+// gave north 13 and the others 12, leaving three cards out. It is read with
+// no checks so that such deals can still be loaded; `Deal` refuses them.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LegacyDeal {
+	pub dealer: Seat,
+	pub vulnerable: Vulnerability,
+	north: Vec<Card>,
+	east: Vec<Card>,
+	south: Vec<Card>,
+	west: Vec<Card>,
+}
+
+impl LegacyDeal {
+	// the deal with the cards left out given to the hands short of 13. The
+	// hands keep every card they had, and the same legacy deal is always
+	// repaired the same way.
+	pub fn repair(self) -> Result<Deal> {
+		let hands = [self.north, self.east, self.south, self.west];
+		let mut builder = Deal::builder()
+			.with_dealer(self.dealer)
+			.with_vulnerability(self.vulnerable);
+		for (seat, cards) in Seat::vec().into_iter().zip(hands) {
+			builder = builder.with_hand(seat, Hand::partial(cards)?);
+		}
+		builder.deal_from_seed(0)
+	}
+}
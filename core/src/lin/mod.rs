@@ -0,0 +1,312 @@
+//! Reading and writing Bridge Base Online hand records in LIN format.
+
+use super::{
+	auction::Auction, cardset::CardSet, Annotation, Bid, Card, Contract, Deal, Error, Hand, Level,
+	Rank, Result, Seat, Suit, Trump, Vulnerability,
+};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+// LIN lists hands starting from South and proceeding clockwise
+const LIN_SEATS: [Seat; 4] = [Seat::South, Seat::West, Seat::North, Seat::East];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+	pub board: Option<String>,
+	pub players: Option<Vec<String>>,
+	pub deal: Deal,
+	// alerts and their explanations are kept as annotations on the calls
+	pub auction: Auction,
+	pub play: Vec<Card>,
+	pub claim: Option<usize>,
+}
+
+impl Record {
+	pub fn new(deal: Deal) -> Self {
+		Record {
+			board: None,
+			players: None,
+			auction: Auction::for_deal(&deal),
+			deal,
+			play: Vec::new(),
+			claim: None,
+		}
+	}
+
+	pub fn parse(s: &str) -> Result<Self> {
+		Self::from_pairs(&pairs(s)?)
+	}
+
+	fn from_pairs(pairs: &[(String, String)]) -> Result<Self> {
+		let md = pairs
+			.iter()
+			.find(|(k, _)| k == "md")
+			.ok_or_else(|| Error::parse("LIN record has no md tag"))?;
+		let vulnerable = match pairs.iter().find(|(k, _)| k == "sv") {
+			Some((_, v)) => Vulnerability::parse_lin(v)?,
+			None => Vulnerability::Neither,
+		};
+		let mut record = Record::new(Deal::parse_lin(&md.1, vulnerable)?);
+
+		for (key, value) in pairs {
+			match key.as_str() {
+				"ah" => record.board = Some(value.clone()),
+				"pn" => record.players = Some(value.split(',').map(String::from).collect()),
+				"mb" => {
+					let annotation = match value.ends_with('!') {
+						true => Annotation::alert(),
+						false => Annotation::default(),
+					};
+					let bid = bid_from_lin(value.trim_end_matches('!'))?;
+					record.auction = record
+						.auction
+						.with_annotated_continuation(bid, annotation)?;
+				}
+				"an" => {
+					let call = match record.auction.len() {
+						0 => return Err(Error::parse("LIN annotation before first call")),
+						len => len - 1,
+					};
+					let alert = record.auction.annotation(call).unwrap().alert;
+					record
+						.auction
+						.annotate(call, Annotation::explained(alert, value.clone()))?;
+				}
+				"pc" => record.play.push(card_from_lin(value)?),
+				"mc" => {
+					let tricks = value
+						.parse()
+						.map_err(|_| Error::parse(format!("invalid LIN claim '{}'", value)))?;
+					record.claim = Some(tricks);
+				}
+				_ => (),
+			}
+		}
+		Ok(record)
+	}
+}
+
+impl fmt::Display for Record {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(players) = &self.players {
+			write!(f, "pn|{}|", players.join(","))?;
+		}
+		write!(f, "st||md|{}|rh||", self.deal.to_lin())?;
+		if let Some(board) = &self.board {
+			write!(f, "ah|{}|", board)?;
+		}
+		write!(f, "sv|{}|", self.deal.vulnerable.to_lin())?;
+		for call in self.auction.calls() {
+			let alert = if call.annotation.alert { "!" } else { "" };
+			write!(f, "mb|{}{}|", bid_to_lin(call.bid), alert)?;
+			if let Some(explanation) = &call.annotation.explanation {
+				// LIN has no way to escape the bar that ends a value
+				write!(f, "an|{}|", explanation.replace('|', "/"))?;
+			}
+		}
+		for (i, card) in self.play.iter().enumerate() {
+			write!(f, "pc|{}{}|", card.suit, card.rank)?;
+			if i % 4 == 3 {
+				write!(f, "pg||")?;
+			}
+		}
+		if let Some(tricks) = self.claim {
+			write!(f, "mc|{}|", tricks)?;
+		}
+		Ok(())
+	}
+}
+
+// parses a LIN document into records, starting a new record at each qx tag
+// or at a repeated md tag
+pub fn parse(s: &str) -> Result<Vec<Record>> {
+	let mut groups: Vec<Vec<(String, String)>> = vec![Vec::new()];
+	for (key, value) in pairs(s)? {
+		let has_deal = groups.last().unwrap().iter().any(|(k, _)| k == "md");
+		if has_deal && (key == "qx" || key == "md") {
+			groups.push(Vec::new());
+		}
+		groups.last_mut().unwrap().push((key, value));
+	}
+	groups
+		.iter()
+		.filter(|g| g.iter().any(|(k, _)| k == "md"))
+		.map(|g| Record::from_pairs(g))
+		.collect()
+}
+
+impl Deal {
+	pub fn parse_lin(md: &str, vulnerable: Vulnerability) -> Result<Deal> {
+		let mut chars = md.trim().chars();
+		let dealer = match chars.next() {
+			Some('1') => Seat::South,
+			Some('2') => Seat::West,
+			Some('3') => Seat::North,
+			Some('4') => Seat::East,
+			_ => return Err(Error::parse(format!("LIN deal '{}' has no dealer", md))),
+		};
+		let hands: Vec<&str> = chars.as_str().split(',').collect();
+		if hands.len() < 3 || hands.len() > 4 {
+			return Err(Error::parse(format!(
+				"LIN deal '{}' must have three or four hands",
+				md
+			)));
+		}
+
+		let mut builder = Deal::builder()
+			.with_dealer(dealer)
+			.with_vulnerability(vulnerable);
+		let mut dealt = CardSet::empty();
+		for (seat, hand) in LIN_SEATS.iter().zip(hands.iter().take(3)) {
+			let hand = Hand::checked(cards_from_lin(hand)?)?;
+			dealt |= hand.cards();
+			builder = builder.with_hand(*seat, hand);
+		}
+		let fourth = hands.get(3).map_or("", |h| h.trim());
+		let fourth = if fourth.is_empty() {
+			// the last hand may be left out, in which case it holds every
+			// card not dealt to the other three
+			Hand::from_cards(dealt.complement())
+		} else {
+			Hand::checked(cards_from_lin(fourth)?)?
+		};
+		builder.with_hand(LIN_SEATS[3], fourth).build()
+	}
+
+	pub fn to_lin(&self) -> String {
+		let dealer = match self.dealer {
+			Seat::South => 1,
+			Seat::West => 2,
+			Seat::North => 3,
+			Seat::East => 4,
+		};
+		let hands = LIN_SEATS
+			.iter()
+			.map(|seat| hand_to_lin(self.hand_for_seat(*seat)))
+			.collect::<Vec<String>>()
+			.join(",");
+		format!("{}{}", dealer, hands)
+	}
+}
+
+impl Vulnerability {
+	pub fn parse_lin(s: &str) -> Result<Vulnerability> {
+		use self::Vulnerability::*;
+		match s {
+			"o" | "O" | "0" | "-" => Ok(Neither),
+			"n" | "N" => Ok(NS),
+			"e" | "E" => Ok(EW),
+			"b" | "B" => Ok(Both),
+			_ => Err(Error::parse(format!("invalid LIN vulnerability '{}'", s))),
+		}
+	}
+
+	pub fn to_lin(self) -> &'static str {
+		use self::Vulnerability::*;
+		match self {
+			Neither => "o",
+			NS => "n",
+			EW => "e",
+			Both => "b",
+		}
+	}
+}
+
+fn hand_to_lin(hand: &Hand) -> String {
+	[Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+		.iter()
+		.map(|suit| format!("{}{}", suit, hand.suit_holding(*suit)))
+		.collect()
+}
+
+fn cards_from_lin(s: &str) -> Result<Vec<Card>> {
+	let mut cards = Vec::new();
+	let mut suit = None;
+	for c in s.trim().chars() {
+		match suit_from_lin(c) {
+			Some(s) => suit = Some(s),
+			None => {
+				let suit = suit.ok_or_else(|| {
+					Error::parse(format!("LIN hand '{}' has a rank before any suit", s))
+				})?;
+				let rank = Rank::parse(&c.to_ascii_uppercase().to_string())?;
+				cards.push(Card { rank, suit });
+			}
+		}
+	}
+	Ok(cards)
+}
+
+fn suit_from_lin(c: char) -> Option<Suit> {
+	match c.to_ascii_uppercase() {
+		'S' => Some(Suit::Spades),
+		'H' => Some(Suit::Hearts),
+		'D' => Some(Suit::Diamonds),
+		'C' => Some(Suit::Clubs),
+		_ => None,
+	}
+}
+
+fn card_from_lin(s: &str) -> Result<Card> {
+	let mut chars = s.trim().chars();
+	match (chars.next().and_then(suit_from_lin), chars.as_str()) {
+		(Some(suit), rank) => Ok(Card {
+			suit,
+			rank: Rank::parse(&rank.to_ascii_uppercase())?,
+		}),
+		_ => Err(Error::parse(format!("invalid LIN card '{}'", s))),
+	}
+}
+
+fn bid_to_lin(bid: Bid) -> String {
+	match bid {
+		Bid::Pass => "p".to_string(),
+		Bid::Double => "d".to_string(),
+		Bid::Redouble => "r".to_string(),
+		Bid::Contract(Contract(level, Trump::NoTrump)) => format!("{}N", level),
+		Bid::Contract(c) => format!("{}", c),
+	}
+}
+
+fn bid_from_lin(s: &str) -> Result<Bid> {
+	let upper = s.trim().to_ascii_uppercase();
+	match upper.as_str() {
+		"P" => Ok(Bid::Pass),
+		"D" | "X" => Ok(Bid::Double),
+		"R" | "XX" => Ok(Bid::Redouble),
+		// the level is one character, so a call opening with any other is
+		// left to the error below rather than split inside it
+		_ if upper.len() >= 2 && upper.is_char_boundary(1) => {
+			let level = Level::parse(&upper[0..1])?;
+			let trump = match &upper[1..] {
+				"N" | "NT" => Trump::NoTrump,
+				suit => Trump::Trump(Suit::parse(suit)?),
+			};
+			Ok(Bid::Contract(Contract(level, trump)))
+		}
+		_ => Err(Error::parse(format!("invalid LIN call '{}'", s))),
+	}
+}
+
+// splits a LIN document into its key/value pairs, ignoring line breaks
+fn pairs(s: &str) -> Result<Vec<(String, String)>> {
+	let joined: String = s.lines().map(|l| l.trim_end_matches('\r')).collect();
+	let fields: Vec<&str> = joined.split('|').collect();
+	let mut pairs = Vec::new();
+	let mut i = 0;
+	while i < fields.len() {
+		let key = fields[i].trim();
+		if key.is_empty() {
+			i += 1;
+			continue;
+		}
+		let value = fields
+			.get(i + 1)
+			.ok_or_else(|| Error::parse(format!("LIN tag '{}' has no value", key)))?;
+		pairs.push((key.to_ascii_lowercase(), value.to_string()));
+		i += 2;
+	}
+	Ok(pairs)
+}
@@ -0,0 +1,118 @@
+use super::{parse, Record};
+use crate::{Annotation, Deal, Error, Seat, Vulnerability};
+
+const RECORD: &str = "pn|South,West,North,East|st||md|3SAKJ6HQJ3DK2CAQ64,S9H754DAQJ965C872,S873HK86DT87CKJT5,|rh||ah|Board 7|sv|e|mb|1C|mb|p|mb|1N!|an|15-17|mb|p|mb|3n|mb|p|mb|p|mb|p|pc|D4|pc|DA|pc|D7|pc|D2|pg||mc|9|";
+
+#[test]
+fn parses_deal_and_derives_fourth_hand() {
+	let record = Record::parse(RECORD).unwrap();
+	let deal = &record.deal;
+	assert_eq!(deal.dealer, Seat::North);
+	assert_eq!(deal.vulnerable, Vulnerability::EW);
+	assert_eq!(format!("{}", deal.south), "AKJ6|QJ3|K2|AQ64");
+	assert_eq!(format!("{}", deal.west), "9|754|AQJ965|872");
+	assert_eq!(format!("{}", deal.north), "873|K86|T87|KJT5");
+	assert_eq!(format!("{}", deal.east), "QT542|AT92|43|93");
+}
+
+#[test]
+fn parses_auction_alerts_and_play() {
+	let record = Record::parse(RECORD).unwrap();
+	assert_eq!(record.board.as_deref(), Some("Board 7"));
+	assert_eq!(record.players.as_ref().map(|p| p.len()), Some(4));
+	assert_eq!(
+		format!("{}", record.auction.bids()),
+		"1C,Pass,1NT!,Pass,3NT,Pass,Pass,Pass"
+	);
+	assert_eq!(
+		record.auction.annotation(2),
+		Some(&Annotation::explained(true, "15-17"))
+	);
+	assert!(record.auction.annotation(1).unwrap().is_empty());
+	assert_eq!(record.auction.dealer(), Seat::North);
+	assert_eq!(record.auction.seat_of(2), Some(Seat::South));
+	assert_eq!(record.play.len(), 4);
+	assert_eq!(
+		format!("{}{}", record.play[1].suit, record.play[1].rank),
+		"DA"
+	);
+	assert_eq!(record.claim, Some(9));
+}
+
+#[test]
+fn write_round_trip() {
+	let record = Record::parse(RECORD).unwrap();
+	let written = format!("{}", record);
+	assert!(written.contains("mb|1N!|an|15-17|"));
+	assert_eq!(Record::parse(&written).unwrap(), record);
+}
+
+#[test]
+fn explanations_cannot_break_the_record() {
+	let mut record = Record::parse(RECORD).unwrap();
+	record
+		.auction
+		.annotate(0, Annotation::explained(false, "2+ clubs | could be 4333"))
+		.unwrap();
+	let written = format!("{}", record);
+	assert!(written.contains("mb|1C|an|2+ clubs / could be 4333|mb|p|"));
+	let read = Record::parse(&written).unwrap();
+	assert_eq!(read.auction.bids().bids(), record.auction.bids().bids());
+	assert_eq!(
+		read.auction.annotation(0),
+		Some(&Annotation::explained(false, "2+ clubs / could be 4333"))
+	);
+	assert_eq!(read.play, record.play);
+	assert_eq!(read.claim, record.claim);
+}
+
+#[test]
+fn deal_round_trip() {
+	let deal = Deal::parse_lin(
+		"2SAKJ6HQJ3DK2CAQ64,S9H754DAQJ965C872,S873HK86DT87CKJT5,SQT542HAT92D43C93",
+		Vulnerability::Both,
+	)
+	.unwrap();
+	assert_eq!(deal.dealer, Seat::West);
+	assert_eq!(
+		Deal::parse_lin(&deal.to_lin(), Vulnerability::Both).unwrap(),
+		deal
+	);
+}
+
+#[test]
+fn rejects_duplicate_cards() {
+	let md = "3SAKJ6HQJ3DK2CAQ64,SAKJ6HQJ3DK2CAQ64,S873HK86DT87CKJT5,";
+	assert!(Deal::parse_lin(md, Vulnerability::Neither).is_err());
+}
+
+#[test]
+fn rejects_illegal_auction() {
+	let lin = "md|3SAKJ6HQJ3DK2CAQ64,S9H754DAQJ965C872,S873HK86DT87CKJT5,|mb|1S|mb|1C|";
+	assert!(Record::parse(lin).is_err());
+}
+
+#[test]
+fn rejects_calls_that_are_not_ascii() {
+	let md = "md|3SAKJ6HQJ3DK2CAQ64,S9H754DAQJ965C872,S873HK86DT87CKJT5,|";
+	for call in ["é", "éS", "1é"] {
+		let lin = format!("{}mb|{}|", md, call);
+		match Record::parse(&lin) {
+			Err(Error::Parse(_)) => {}
+			other => panic!("expected a parse error for '{}', got {:?}", call, other),
+		}
+	}
+}
+
+#[test]
+fn parses_multiple_records() {
+	let lin = format!(
+		"qx|o1|{}\nqx|c1|{}\n",
+		RECORD,
+		RECORD.replace("sv|e|", "sv|b|")
+	);
+	let records = parse(&lin).unwrap();
+	assert_eq!(records.len(), 2);
+	assert_eq!(records[0].deal.vulnerable, Vulnerability::EW);
+	assert_eq!(records[1].deal.vulnerable, Vulnerability::Both);
+}
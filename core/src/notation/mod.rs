@@ -0,0 +1,323 @@
+//! The names of suits, ranks and calls, for reading what people type and
+//! writing in their language.
+//!
+//! A notation knows one or more names for each suit, for no trump, for each
+//! rank and for pass, double and redouble. The first name is the one written;
+//! the others are accepted when parsing. Parsing ignores case and surrounding
+//! space, and every notation also accepts the suit symbols and `10` for the
+//! ten. `Suit::parse`, `Rank::parse`, `Bid::parse` and `Hand::parse` read the
+//! English notation.
+
+use super::{Bid, Card, Contract, Error, Hand, Level, Rank, Result, Suit, Trump};
+use std::sync::OnceLock;
+
+#[cfg(test)]
+mod tests;
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+const RANKS: [Rank; 13] = [
+	Rank::Ace,
+	Rank::King,
+	Rank::Queen,
+	Rank::Jack,
+	Rank::Ten,
+	Rank::Nine,
+	Rank::Eight,
+	Rank::Seven,
+	Rank::Six,
+	Rank::Five,
+	Rank::Four,
+	Rank::Three,
+	Rank::Two,
+];
+
+// the solid and outline symbols of each suit, spades first
+const SYMBOLS: [[&str; 2]; 4] = [
+	["\u{2660}", "\u{2664}"],
+	["\u{2665}", "\u{2661}"],
+	["\u{2666}", "\u{2662}"],
+	["\u{2663}", "\u{2667}"],
+];
+
+fn suit_index(suit: Suit) -> usize {
+	SUITS.iter().position(|s| *s == suit).unwrap()
+}
+
+fn rank_index(rank: Rank) -> usize {
+	RANKS.iter().position(|r| *r == rank).unwrap()
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+	names.iter().map(|n| n.to_string()).collect()
+}
+
+// the English notation, shared by the parse functions of the card types
+pub(crate) fn english() -> &'static Notation {
+	static ENGLISH: OnceLock<Notation> = OnceLock::new();
+	ENGLISH.get_or_init(Notation::english)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notation {
+	// spades, hearts, diamonds and clubs
+	suits: [Vec<String>; 4],
+	no_trump: Vec<String>,
+	// ace down to two
+	ranks: [Vec<String>; 13],
+	pass: Vec<String>,
+	double: Vec<String>,
+	redouble: Vec<String>,
+}
+
+impl Notation {
+	// S, H, D and C, as the types print themselves
+	pub fn english() -> Self {
+		Notation {
+			suits: [names(&["S"]), names(&["H"]), names(&["D"]), names(&["C"])],
+			no_trump: names(&["NT", "N"]),
+			ranks: [
+				names(&["A"]),
+				names(&["K"]),
+				names(&["Q"]),
+				names(&["J"]),
+				names(&["T"]),
+				names(&["9"]),
+				names(&["8"]),
+				names(&["7"]),
+				names(&["6"]),
+				names(&["5"]),
+				names(&["4"]),
+				names(&["3"]),
+				names(&["2"]),
+			],
+			pass: names(&["Pass", "P"]),
+			double: names(&["Dbl", "X", "D", "Double"]),
+			redouble: names(&["Rdbl", "XX", "R", "Redouble"]),
+		}
+	}
+
+	// English, written with the suit symbols
+	pub fn unicode() -> Self {
+		let mut notation = Self::english();
+		for (i, suit) in notation.suits.iter_mut().enumerate() {
+			suit.insert(0, SYMBOLS[i][0].to_string());
+		}
+		notation
+	}
+
+	// pique, cœur, carreau and trèfle, with sans atout, roi, dame and valet
+	pub fn french() -> Self {
+		Self::english()
+			.with_suit(Suit::Spades, &["P"])
+			.with_suit(Suit::Hearts, &["C"])
+			.with_suit(Suit::Diamonds, &["K"])
+			.with_suit(Suit::Clubs, &["T"])
+			.with_no_trump(&["SA"])
+			.with_rank(Rank::King, &["R"])
+			.with_rank(Rank::Queen, &["D"])
+			.with_rank(Rank::Jack, &["V"])
+			.with_rank(Rank::Ten, &["10"])
+			.with_calls(&["Passe", "P"], &["Contre", "X"], &["Surcontre", "XX"])
+	}
+
+	pub fn with_suit(mut self, suit: Suit, names: &[&str]) -> Self {
+		self.suits[suit_index(suit)] = self::names(names);
+		self
+	}
+
+	pub fn with_no_trump(mut self, names: &[&str]) -> Self {
+		self.no_trump = self::names(names);
+		self
+	}
+
+	pub fn with_rank(mut self, rank: Rank, names: &[&str]) -> Self {
+		self.ranks[rank_index(rank)] = self::names(names);
+		self
+	}
+
+	pub fn with_calls(mut self, pass: &[&str], double: &[&str], redouble: &[&str]) -> Self {
+		self.pass = names(pass);
+		self.double = names(double);
+		self.redouble = names(redouble);
+		self
+	}
+
+	// the names of each suit, including the symbols
+	fn suit_names(&self) -> impl Iterator<Item = (Suit, &str)> {
+		SUITS.iter().enumerate().flat_map(move |(i, suit)| {
+			self.suits[i]
+				.iter()
+				.map(|n| n.as_str())
+				.chain(SYMBOLS[i].iter().cloned())
+				.map(move |n| (*suit, n))
+		})
+	}
+
+	// the names of each rank, including `10`
+	fn rank_names(&self) -> impl Iterator<Item = (Rank, &str)> {
+		RANKS.iter().enumerate().flat_map(move |(i, rank)| {
+			let ten = match rank {
+				Rank::Ten => Some("10"),
+				_ => None,
+			};
+			self.ranks[i]
+				.iter()
+				.map(|n| n.as_str())
+				.chain(ten)
+				.map(move |n| (*rank, n))
+		})
+	}
+
+	pub fn parse_suit(&self, s: &str) -> Result<Suit> {
+		let lower = s.trim().to_lowercase();
+		self.suit_names()
+			.find(|(_, name)| name.to_lowercase() == lower)
+			.map(|(suit, _)| suit)
+			.ok_or_else(|| Error::parse(format!("failed to parse suit string '{}'", s)))
+	}
+
+	pub fn parse_trump(&self, s: &str) -> Result<Trump> {
+		let lower = s.trim().to_lowercase();
+		if self.no_trump.iter().any(|n| n.to_lowercase() == lower) {
+			return Ok(Trump::NoTrump);
+		}
+		Ok(Trump::Trump(self.parse_suit(s)?))
+	}
+
+	pub fn parse_rank(&self, s: &str) -> Result<Rank> {
+		let lower = s.trim().to_lowercase();
+		self.rank_names()
+			.find(|(_, name)| name.to_lowercase() == lower)
+			.map(|(rank, _)| rank)
+			.ok_or_else(|| Error::parse(format!("invalid rank string '{}'", s)))
+	}
+
+	pub fn parse_contract(&self, s: &str) -> Result<Contract> {
+		let s = s.trim();
+		let mut chars = s.chars();
+		let level = match chars.next() {
+			Some(level) => Level::parse(&level.to_string())?,
+			None => {
+				return Err(Error::parse(
+					"contract string must have length of at least 2",
+				))
+			}
+		};
+		let strain = chars.as_str();
+		if strain.is_empty() {
+			return Err(Error::parse(
+				"contract string must have length of at least 2",
+			));
+		}
+		Ok(Contract(level, self.parse_trump(strain)?))
+	}
+
+	pub fn parse_bid(&self, s: &str) -> Result<Bid> {
+		let lower = s.trim().to_lowercase();
+		let is = |names: &[String]| names.iter().any(|n| n.to_lowercase() == lower);
+		if is(&self.pass) {
+			Ok(Bid::Pass)
+		} else if is(&self.double) {
+			Ok(Bid::Double)
+		} else if is(&self.redouble) {
+			Ok(Bid::Redouble)
+		} else {
+			Ok(Bid::Contract(self.parse_contract(s)?))
+		}
+	}
+
+	// the ranks held in one suit, such as `AKT2` or `A K 10 2`
+	pub fn parse_holding(&self, suit: Suit, s: &str) -> Result<Vec<Card>> {
+		let lower = s.to_lowercase();
+		let mut rest = lower.trim();
+		let mut cards = Vec::new();
+		while !rest.is_empty() {
+			// names can share a first letter, so take the longest that fits
+			let (rank, len) = self
+				.rank_names()
+				.map(|(rank, name)| (rank, name.to_lowercase()))
+				.filter(|(_, name)| !name.is_empty() && rest.starts_with(name.as_str()))
+				.map(|(rank, name)| (rank, name.len()))
+				.max_by_key(|(_, len)| *len)
+				.ok_or_else(|| Error::parse(format!("invalid ranks '{}'", s)))?;
+			cards.push(Card { rank, suit });
+			rest = rest[len..].trim_start();
+		}
+		Ok(cards)
+	}
+
+	// a hand written suit by suit from spades down, separated by `|`, with
+	// all 13 cards and none twice
+	pub fn parse_hand(&self, s: &str) -> Result<Hand> {
+		Hand::checked(self.parse_cards(s)?)
+	}
+
+	// the same for part of a hand, with at most 13 cards
+	pub fn parse_partial_hand(&self, s: &str) -> Result<Hand> {
+		Hand::partial(self.parse_cards(s)?)
+	}
+
+	fn parse_cards(&self, s: &str) -> Result<Vec<Card>> {
+		let holdings: Vec<&str> = s.split('|').collect();
+		if holdings.len() != 4 {
+			return Err(Error::parse(format!(
+				"a hand needs four suits separated by '|', not '{}'",
+				s
+			)));
+		}
+		let mut cards = Vec::new();
+		for (suit, holding) in SUITS.iter().zip(holdings) {
+			cards.extend(self.parse_holding(*suit, holding)?);
+		}
+		Ok(cards)
+	}
+
+	pub fn write_suit(&self, suit: Suit) -> &str {
+		&self.suits[suit_index(suit)][0]
+	}
+
+	pub fn write_trump(&self, trump: Trump) -> &str {
+		match trump {
+			Trump::NoTrump => &self.no_trump[0],
+			Trump::Trump(suit) => self.write_suit(suit),
+		}
+	}
+
+	pub fn write_rank(&self, rank: Rank) -> &str {
+		&self.ranks[rank_index(rank)][0]
+	}
+
+	pub fn write_card(&self, card: Card) -> String {
+		format!(
+			"{}{}",
+			self.write_suit(card.suit),
+			self.write_rank(card.rank)
+		)
+	}
+
+	pub fn write_bid(&self, bid: Bid) -> String {
+		match bid {
+			Bid::Pass => self.pass[0].clone(),
+			Bid::Double => self.double[0].clone(),
+			Bid::Redouble => self.redouble[0].clone(),
+			Bid::Contract(contract) => {
+				format!("{}{}", contract.0.number(), self.write_trump(contract.1))
+			}
+		}
+	}
+
+	pub fn write_hand(&self, hand: &Hand) -> String {
+		SUITS
+			.iter()
+			.map(|suit| {
+				hand.cards()
+					.suit(*suit)
+					.iter()
+					.map(|card| self.write_rank(card.rank))
+					.collect::<String>()
+			})
+			.collect::<Vec<String>>()
+			.join("|")
+	}
+}
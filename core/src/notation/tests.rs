@@ -0,0 +1,144 @@
+use super::Notation;
+use crate::{Bid, Card, Hand, Rank, Suit};
+
+fn bid(s: &str) -> Bid {
+	Bid::parse(s).unwrap()
+}
+
+macro_rules! test_english_bids {
+    ($($name:ident: $value:expr,)*) => {
+        mod english_bids {
+            use super::super::Notation;
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                let notation = Notation::english();
+                assert_eq!(notation.parse_bid(input).unwrap(), super::bid(expected));
+            }
+        )*
+        }
+    }
+}
+
+test_english_bids! {
+	lower_case: ("1nt", "1NT"),
+	short_no_trump: ("3N", "3NT"),
+	short_no_trump_lower: ("3n", "3NT"),
+	lower_suit: ("2s", "2S"),
+	surrounding_space: (" 4H ", "4H"),
+	spade_symbol: ("1\u{2660}", "1S"),
+	heart_symbol: ("2\u{2665}", "2H"),
+	outline_diamond: ("3\u{2662}", "3D"),
+	club_symbol: ("1\u{2663}", "1C"),
+}
+
+macro_rules! test_french_bids {
+    ($($name:ident: $value:expr,)*) => {
+        mod french_bids {
+            use super::super::Notation;
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                let notation = Notation::french();
+                assert_eq!(notation.parse_bid(input).unwrap(), super::bid(expected));
+            }
+        )*
+        }
+    }
+}
+
+test_french_bids! {
+	pique: ("1P", "1S"),
+	coeur: ("2C", "2H"),
+	carreau: ("3K", "3D"),
+	trefle: ("1T", "1C"),
+	sans_atout: ("3SA", "3NT"),
+	passe: ("passe", "Pass"),
+	p: ("P", "Pass"),
+	contre: ("Contre", "Dbl"),
+	surcontre: ("XX", "Rdbl"),
+	symbol: ("4\u{2660}", "4S"),
+}
+
+#[test]
+fn rejects_unknown_names() {
+	let english = Notation::english();
+	assert!(english.parse_bid("1Z").is_err());
+	assert!(english.parse_bid("8S").is_err());
+	assert!(english.parse_bid("1").is_err());
+	assert!(english.parse_bid("").is_err());
+	assert!(english.parse_bid("1SA").is_err());
+	assert!(Notation::french().parse_bid("1S").is_err());
+	assert!(Notation::french().parse_bid("Dbl").is_err());
+}
+
+#[test]
+fn parses_cards() {
+	let english = Notation::english();
+	assert_eq!(english.parse_suit("h").unwrap(), Suit::Hearts);
+	assert_eq!(english.parse_rank("t").unwrap(), Rank::Ten);
+	assert_eq!(english.parse_rank("10").unwrap(), Rank::Ten);
+	assert_eq!(
+		english.parse_holding(Suit::Spades, "a 10 2").unwrap(),
+		vec![
+			Card {
+				rank: Rank::Ace,
+				suit: Suit::Spades
+			},
+			Card {
+				rank: Rank::Ten,
+				suit: Suit::Spades
+			},
+			Card {
+				rank: Rank::Two,
+				suit: Suit::Spades
+			},
+		]
+	);
+	let hand = Hand::parse("AKQ2|KJ4|Q93|K32").unwrap();
+	assert_eq!(english.parse_hand("akq2|kj4|q93|k32").unwrap(), hand);
+	assert_eq!(
+		Notation::french().parse_hand("ARD2|RV4|D93|R32").unwrap(),
+		hand
+	);
+	assert!(english.parse_hand("AKQ2|KJ4|Q93").is_err());
+	assert!(english.parse_hand("AKZ|||").is_err());
+}
+
+#[test]
+fn writes_in_the_notation() {
+	let hand = Hand::parse_partial("AKT2|QJ4|93|K32").unwrap();
+	let english = Notation::english();
+	assert_eq!(english.write_hand(&hand), hand.to_string());
+	assert_eq!(english.write_bid(bid("3NT")), "3NT");
+	assert_eq!(english.write_bid(bid("x")), "Dbl");
+
+	let french = Notation::french();
+	assert_eq!(french.write_hand(&hand), "AR102|DV4|93|R32");
+	assert_eq!(french.write_bid(bid("3NT")), "3SA");
+	assert_eq!(french.write_bid(bid("2H")), "2C");
+	assert_eq!(french.write_bid(bid("Rdbl")), "Surcontre");
+	assert_eq!(
+		french.write_card(Card {
+			rank: Rank::Queen,
+			suit: Suit::Clubs
+		}),
+		"TD"
+	);
+
+	let unicode = Notation::unicode();
+	assert_eq!(unicode.write_bid(bid("4S")), "4\u{2660}");
+	assert_eq!(unicode.parse_bid("4s").unwrap(), bid("4S"));
+}
+
+#[test]
+fn custom_names() {
+	let notation = Notation::english()
+		.with_suit(Suit::Spades, &["Sp", "S"])
+		.with_no_trump(&["NT", "SA", "N"]);
+	assert_eq!(notation.parse_bid("1sp").unwrap(), bid("1S"));
+	assert_eq!(notation.parse_bid("2sa").unwrap(), bid("2NT"));
+	assert_eq!(notation.write_bid(bid("1S")), "1Sp");
+}
@@ -0,0 +1,259 @@
+//! Par: the result of a deal when both sides bid perfectly with knowledge of
+//! the double-dummy table, sacrificing whenever it costs less than letting the
+//! opponents play.
+
+use super::{
+	dd::Table,
+	scoring::{declarer_score, Doubling},
+	Contract, Deal, Level, Seat, Suit, Trump, Vulnerability,
+};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+const LEVELS: [Level; 7] = [
+	Level::One,
+	Level::Two,
+	Level::Three,
+	Level::Four,
+	Level::Five,
+	Level::Six,
+	Level::Seven,
+];
+
+// strains in bidding order
+const STRAINS: [Trump; 5] = [
+	Trump::Trump(Suit::Clubs),
+	Trump::Trump(Suit::Diamonds),
+	Trump::Trump(Suit::Hearts),
+	Trump::Trump(Suit::Spades),
+	Trump::NoTrump,
+];
+
+const CONTRACTS: usize = 35;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ParContract {
+	pub contract: Contract,
+	pub declarer: Seat,
+	// contracts that go down are doubled
+	pub doubling: Doubling,
+	pub tricks: usize,
+}
+
+impl fmt::Display for ParContract {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}{}", self.contract, self.doubling)?;
+		let needed = self.contract.0.number() + 6;
+		if self.tricks >= needed {
+			match self.tricks - needed {
+				0 => write!(f, "=")?,
+				over => write!(f, "+{}", over)?,
+			}
+		} else {
+			write!(f, "-{}", needed - self.tricks)?;
+		}
+		write!(f, " by {}", self.declarer)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Par {
+	// north-south's score, negative when east-west score
+	pub score: i32,
+	// every contract that reaches the par score, empty when the deal is
+	// passed out
+	pub contracts: Vec<ParContract>,
+}
+
+impl Par {
+	pub fn new(table: &Table, dealer: Seat, vulnerable: Vulnerability) -> Self {
+		Auction::new(table, dealer, vulnerable).par()
+	}
+}
+
+impl fmt::Display for Par {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.contracts.is_empty() {
+			return write!(f, "passed out");
+		}
+		if self.score >= 0 {
+			write!(f, "NS +{}:", self.score)?;
+		} else {
+			write!(f, "EW +{}:", -self.score)?;
+		}
+		for (idx, contract) in self.contracts.iter().enumerate() {
+			let sep = if idx == 0 { " " } else { ", " };
+			write!(f, "{}{}", sep, contract)?;
+		}
+		Ok(())
+	}
+}
+
+impl Deal {
+	pub fn par(&self) -> Par {
+		Par::new(&Table::new(self), self.dealer, self.vulnerable)
+	}
+}
+
+// the contest for the contract, with contracts indexed in bidding order and
+// sides numbered 0 for north-south and 1 for east-west
+struct Auction {
+	// the side's declarer and tricks in each strain
+	declarers: [[(Seat, usize); 5]; 2],
+	vulnerable: [bool; 2],
+	first: usize,
+	// the best the side holding each contract can do once the opponents
+	// have had their chance to bid over it
+	values: [[i32; CONTRACTS]; 2],
+}
+
+impl Auction {
+	fn new(table: &Table, dealer: Seat, vulnerable: Vulnerability) -> Self {
+		let mut declarers = [[(Seat::North, 0); 5]; 2];
+		for (side, strains) in declarers.iter_mut().enumerate() {
+			// the partner nearer the dealer gets to bid the strain first
+			let mut seats = [Seat::vec()[side], Seat::vec()[side + 2]];
+			seats.sort_by_key(|s| (s.index() + 4 - dealer.index()) % 4);
+			for (strain_idx, trump) in STRAINS.iter().enumerate() {
+				let tricks = seats.map(|s| table.tricks(s, *trump));
+				strains[strain_idx] = if tricks[1] > tricks[0] {
+					(seats[1], tricks[1])
+				} else {
+					(seats[0], tricks[0])
+				};
+			}
+		}
+		let vulnerable = match vulnerable {
+			Vulnerability::Neither => [false, false],
+			Vulnerability::NS => [true, false],
+			Vulnerability::EW => [false, true],
+			Vulnerability::Both => [true, true],
+		};
+		let mut auction = Auction {
+			declarers,
+			vulnerable,
+			first: dealer.index() % 2,
+			values: [[0; CONTRACTS]; 2],
+		};
+		for idx in (0..CONTRACTS).rev() {
+			for side in 0..2 {
+				// the opponents either let the contract play or outbid it
+				let outbid = (idx + 1..CONTRACTS)
+					.map(|j| auction.values[1 - side][j])
+					.max();
+				let played = auction.score(idx, side);
+				auction.values[side][idx] = match outbid {
+					Some(outbid) => played.min(-outbid),
+					None => played,
+				};
+			}
+		}
+		auction
+	}
+
+	// the declaring side's score when the contract is played, doubled if it
+	// goes down
+	fn score(&self, idx: usize, side: usize) -> i32 {
+		let (_, tricks) = self.declarers[side][idx % 5];
+		declarer_score(
+			contract(idx),
+			doubling(idx, tricks),
+			tricks,
+			self.vulnerable[side],
+		)
+	}
+
+	fn best_bid(&self, side: usize) -> i32 {
+		*self.values[side].iter().max().unwrap()
+	}
+
+	fn par(&self) -> Par {
+		let first = self.first;
+		let second = 1 - first;
+		let first_bid = self.best_bid(first);
+		let second_bid = self.best_bid(second);
+		// if the first side passes the second can bid, or pass and leave the
+		// first side to bid or pass the deal out
+		let waiting = (-second_bid).min(first_bid.max(0));
+		let mut contracts = Vec::new();
+		let (side, value) = if first_bid >= waiting {
+			(first, first_bid)
+		} else if second_bid > 0 {
+			(second, second_bid)
+		} else {
+			return Par {
+				score: 0,
+				contracts,
+			};
+		};
+		let mut seen = [[false; CONTRACTS]; 2];
+		for idx in 0..CONTRACTS {
+			if self.values[side][idx] == value {
+				self.collect(idx, side, &mut seen, &mut contracts);
+			}
+		}
+		// a higher level of the same strain scoring the same is no different
+		// from overtricks in the lower one
+		contracts.sort_by_key(|c: &ParContract| c.contract.0.number());
+		let mut par: Vec<ParContract> = Vec::new();
+		for contract in contracts {
+			let same_strain = par.iter().any(|c| {
+				c.contract.1 == contract.contract.1
+					&& c.declarer.index() % 2 == contract.declarer.index() % 2
+			});
+			if !same_strain {
+				par.push(contract);
+			}
+		}
+		let score = if side == 0 { value } else { -value };
+		Par {
+			score,
+			contracts: par,
+		}
+	}
+
+	// follows every line of best play from `side` bidding contract `idx` and
+	// records the contracts they end in
+	fn collect(
+		&self,
+		idx: usize,
+		side: usize,
+		seen: &mut [[bool; CONTRACTS]; 2],
+		out: &mut Vec<ParContract>,
+	) {
+		if seen[side][idx] {
+			return;
+		}
+		seen[side][idx] = true;
+		let value = self.values[side][idx];
+		if self.score(idx, side) == value {
+			let (declarer, tricks) = self.declarers[side][idx % 5];
+			out.push(ParContract {
+				contract: contract(idx),
+				declarer,
+				doubling: doubling(idx, tricks),
+				tricks,
+			});
+		}
+		for j in idx + 1..CONTRACTS {
+			if self.values[1 - side][j] == -value {
+				self.collect(j, 1 - side, seen, out);
+			}
+		}
+	}
+}
+
+fn contract(idx: usize) -> Contract {
+	Contract(LEVELS[idx / 5], STRAINS[idx % 5])
+}
+
+// the opponents double every contract that goes down, and nothing else
+fn doubling(idx: usize, tricks: usize) -> Doubling {
+	if tricks < idx / 5 + 7 {
+		Doubling::Doubled
+	} else {
+		Doubling::Undoubled
+	}
+}
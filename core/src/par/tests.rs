@@ -0,0 +1,84 @@
+use super::Par;
+use crate::{dd::Table, scoring::Doubling, Level, Seat, Vulnerability};
+
+// tricks for each declarer in NT, spades, hearts, diamonds and clubs
+fn table(ns: [usize; 5], ew: [usize; 5]) -> Table {
+	Table::from_tricks([ns, ew, ns, ew])
+}
+
+#[test]
+fn game_without_sacrifice() {
+	let table = table([6, 10, 4, 3, 3], [5, 3, 8, 8, 8]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	assert_eq!(par.score, 420);
+	assert_eq!(format!("{}", par), "NS +420: 4S= by North");
+}
+
+#[test]
+fn overtricks_shown_at_lowest_level() {
+	let table = table([11, 7, 7, 7, 7], [2, 6, 6, 6, 6]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	assert_eq!(format!("{}", par), "NS +460: 3NT+2 by North");
+}
+
+#[test]
+fn cheap_sacrifice() {
+	let table = table([6, 10, 4, 3, 3], [5, 3, 9, 9, 9]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	assert_eq!(par.score, 300);
+	assert_eq!(par.contracts.len(), 3);
+	assert!(par
+		.contracts
+		.iter()
+		.all(|c| c.doubling == Doubling::Doubled && c.tricks == 9));
+	assert_eq!(format!("{}", par.contracts[0]), "5Cx-2 by East");
+
+	// vulnerable, the same sacrifice costs more than the game
+	let par = Par::new(&table, Seat::North, Vulnerability::EW);
+	assert_eq!(format!("{}", par), "NS +420: 4S= by North");
+}
+
+#[test]
+fn sacrifice_pushes_to_slam_level() {
+	// north-south make eleven tricks, so they bid on over a five-level save
+	let table = table([6, 11, 3, 3, 3], [5, 2, 10, 10, 10]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	assert_eq!(par.score, 300);
+	assert!(par
+		.contracts
+		.iter()
+		.all(|c| c.doubling == Doubling::Doubled && c.contract.0 == Level::Six));
+}
+
+#[test]
+fn dealer_side_gets_first_bid() {
+	let table = table([7, 6, 6, 6, 6], [7, 6, 6, 6, 6]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	assert_eq!(format!("{}", par), "NS +90: 1NT= by North");
+	let par = Par::new(&table, Seat::West, Vulnerability::Neither);
+	assert_eq!(format!("{}", par), "EW +90: 1NT= by West");
+}
+
+#[test]
+fn declarer_with_more_tricks() {
+	let table = Table::from_tricks([
+		[9, 9, 6, 6, 6],
+		[4, 4, 7, 7, 7],
+		[10, 9, 6, 6, 6],
+		[4, 4, 7, 7, 7],
+	]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	assert_eq!(format!("{}", par), "NS +430: 3NT+1 by South");
+}
+
+#[test]
+fn slam_from_dd_table() {
+	let table = Table::from_tricks([
+		[3, 0, 3, 3, 4],
+		[10, 12, 9, 9, 9],
+		[2, 0, 3, 3, 3],
+		[9, 11, 9, 9, 8],
+	]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Both);
+	assert_eq!(format!("{}", par), "EW +1430: 6S= by East");
+}
@@ -0,0 +1,537 @@
+//! Reading and writing boards in Portable Bridge Notation (PBN).
+
+use super::{
+	auction::Auction, notation, Annotation, Bid, BidSequence, Deal, Error, Hand, Result, Seat,
+	Suit, Vulnerability,
+};
+use std::{
+	fmt,
+	io::{BufRead, Lines},
+};
+
+#[cfg(test)]
+mod tests;
+
+// tags that are interpreted into the fields of a Game rather than kept as-is
+const INTERPRETED_TAGS: [&str; 5] = ["Deal", "Dealer", "Vulnerable", "Auction", "Note"];
+
+// the tags naming the event, board and players, which are written in this
+// order ahead of Dealer, Vulnerable and Deal as PBN exports them
+const ROSTER_TAGS: [&str; 9] = [
+	"Event", "Site", "Date", "Round", "Board", "West", "North", "East", "South",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+	pub name: String,
+	pub value: String,
+}
+
+impl Tag {
+	pub fn new<N: Into<String>, V: Into<String>>(name: N, value: V) -> Self {
+		Tag {
+			name: name.into(),
+			value: value.into(),
+		}
+	}
+}
+
+impl fmt::Display for Tag {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let escaped = self.value.replace('\\', "\\\\").replace('"', "\\\"");
+		write!(f, "[{} \"{}\"]", self.name, escaped)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+	pub tags: Vec<Tag>,
+	pub deal: Deal,
+	// alerts and notes are kept as annotations on the calls
+	pub auction: Option<Auction>,
+}
+
+impl Game {
+	pub fn new(deal: Deal) -> Self {
+		Game {
+			tags: Vec::new(),
+			deal,
+			auction: None,
+		}
+	}
+
+	pub fn parse(s: &str) -> Result<Self> {
+		Self::from_sections(tokenize(s)?)
+	}
+
+	pub fn tag(&self, name: &str) -> Option<&str> {
+		self.tags
+			.iter()
+			.find(|t| t.name == name)
+			.map(|t| t.value.as_str())
+	}
+
+	fn from_sections(sections: Vec<Section>) -> Result<Self> {
+		let find = |name: &str| sections.iter().find(|s| s.tag.name == name);
+
+		let deal_section = find("Deal").ok_or_else(|| Error::parse("PBN game has no Deal tag"))?;
+		let auction_section = find("Auction");
+
+		let dealer = match (find("Dealer"), auction_section) {
+			(Some(s), _) => Seat::parse_pbn(&s.tag.value)?,
+			(None, Some(s)) => Seat::parse_pbn(&s.tag.value)?,
+			(None, None) => Seat::North,
+		};
+		let vulnerable = match find("Vulnerable") {
+			Some(s) => Vulnerability::parse_pbn(&s.tag.value)?,
+			None => Vulnerability::Neither,
+		};
+		let deal = Deal::parse_pbn(&deal_section.tag.value, dealer, vulnerable)?;
+
+		let (mut auction, refs) = match auction_section {
+			Some(s) => {
+				// the Auction tag names the seat that made the first call
+				let first = Seat::parse_pbn(&s.tag.value)?;
+				let (bids, refs) = parse_auction(&s.tokens)?;
+				(Some(Auction::from_bids(first, vulnerable, bids)), refs)
+			}
+			None => (None, Vec::new()),
+		};
+
+		let mut note_texts = Vec::new();
+		for section in sections.iter().filter(|s| s.tag.name == "Note") {
+			let mut parts = section.tag.value.splitn(2, ':');
+			let num = parts.next().unwrap_or("");
+			let text = parts
+				.next()
+				.ok_or_else(|| Error::parse(format!("invalid note '{}'", section.tag.value)))?;
+			note_texts.push((num.to_string(), text.to_string()));
+		}
+		for (call, num) in refs {
+			let text = note_texts
+				.iter()
+				.find(|(n, _)| *n == num)
+				.map(|(_, text)| text)
+				.ok_or_else(|| Error::parse(format!("auction refers to missing note {}", num)))?;
+			// refs only exist when there is an auction
+			let auction = auction.as_mut().unwrap();
+			let mut annotation = auction.annotation(call).unwrap().clone();
+			annotation.explanation = Some(match annotation.explanation {
+				Some(prev) => format!("{}; {}", prev, text),
+				None => text.clone(),
+			});
+			auction.annotate(call, annotation)?;
+		}
+
+		let tags = sections
+			.into_iter()
+			.map(|s| s.tag)
+			.filter(|t| !INTERPRETED_TAGS.contains(&t.name.as_str()))
+			.collect();
+
+		Ok(Game {
+			tags,
+			deal,
+			auction,
+		})
+	}
+}
+
+impl fmt::Display for Game {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let (mut roster, rest): (Vec<&Tag>, Vec<&Tag>) = self
+			.tags
+			.iter()
+			.partition(|t| ROSTER_TAGS.contains(&t.name.as_str()));
+		roster.sort_by_key(|t| ROSTER_TAGS.iter().position(|name| *name == t.name));
+		for tag in roster {
+			writeln!(f, "{}", tag)?;
+		}
+		writeln!(f, "{}", Tag::new("Dealer", self.deal.dealer.to_pbn()))?;
+		writeln!(
+			f,
+			"{}",
+			Tag::new("Vulnerable", self.deal.vulnerable.to_pbn())
+		)?;
+		writeln!(f, "{}", Tag::new("Deal", self.deal.to_pbn()))?;
+		for tag in rest {
+			writeln!(f, "{}", tag)?;
+		}
+
+		let auction = match &self.auction {
+			Some(auction) => auction,
+			None => return Ok(()),
+		};
+		writeln!(f, "{}", Tag::new("Auction", auction.dealer().to_pbn()))?;
+		let bids = auction.bids();
+
+		// number the distinct explanations in order of first use
+		let mut numbered: Vec<&str> = Vec::new();
+		for annotation in bids.annotations() {
+			if let Some(text) = &annotation.explanation {
+				if !numbered.contains(&text.as_str()) {
+					numbered.push(text);
+				}
+			}
+		}
+
+		for (row_idx, row) in bids.bids().chunks(4).enumerate() {
+			let calls: Vec<String> = row
+				.iter()
+				.enumerate()
+				.map(|(i, bid)| {
+					let call = row_idx * 4 + i;
+					let annotation = &bids.annotations()[call];
+					let mut s = bid_to_pbn(*bid);
+					if annotation.alert {
+						s.push('!');
+					}
+					if let Some(text) = &annotation.explanation {
+						let num = numbered.iter().position(|t| t == text).unwrap() + 1;
+						s.push_str(&format!(" ={}=", num));
+					}
+					s
+				})
+				.collect();
+			writeln!(f, "{}", calls.join(" "))?;
+		}
+		for (i, text) in numbered.iter().enumerate() {
+			writeln!(f, "{}", Tag::new("Note", format!("{}:{}", i + 1, text)))?;
+		}
+		Ok(())
+	}
+}
+
+pub struct Reader<R> {
+	lines: Lines<R>,
+	prev_tags: Vec<Tag>,
+}
+
+impl<R: BufRead> Reader<R> {
+	pub fn new(r: R) -> Self {
+		Reader {
+			lines: r.lines(),
+			prev_tags: Vec::new(),
+		}
+	}
+
+	// reads the raw text of the next game, which ends at the first empty line
+	// outside of a commentary block
+	fn next_game_text(&mut self) -> Result<Option<String>> {
+		let mut text = String::new();
+		let mut in_comment = false;
+		for line in &mut self.lines {
+			let line = line?;
+			if !in_comment && line.starts_with('%') {
+				continue;
+			}
+			if !in_comment && line.trim().is_empty() {
+				if text.trim().is_empty() {
+					continue;
+				}
+				return Ok(Some(text));
+			}
+			for c in line.chars() {
+				match c {
+					'{' => in_comment = true,
+					'}' => in_comment = false,
+					_ => (),
+				}
+			}
+			text.push_str(&line);
+			text.push('\n');
+		}
+		if text.trim().is_empty() {
+			Ok(None)
+		} else {
+			Ok(Some(text))
+		}
+	}
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+	type Item = Result<Game>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let text = match self.next_game_text() {
+			Ok(Some(text)) => text,
+			Ok(None) => return None,
+			Err(e) => return Some(Err(e)),
+		};
+		let mut sections = match tokenize(&text) {
+			Ok(sections) => sections,
+			Err(e) => return Some(Err(e)),
+		};
+
+		// a tag value of "#" inherits the value from the previous game
+		for section in sections.iter_mut() {
+			if section.tag.value == "#" {
+				if let Some(prev) = self.prev_tags.iter().find(|t| t.name == section.tag.name) {
+					section.tag.value = prev.value.clone();
+				}
+			}
+		}
+		self.prev_tags = sections.iter().map(|s| s.tag.clone()).collect();
+
+		Some(Game::from_sections(sections))
+	}
+}
+
+pub fn parse(s: &str) -> Result<Vec<Game>> {
+	Reader::new(s.as_bytes()).collect()
+}
+
+pub fn write<W: std::io::Write>(w: &mut W, games: &[Game]) -> Result<()> {
+	for (i, game) in games.iter().enumerate() {
+		if i > 0 {
+			writeln!(w)?;
+		}
+		write!(w, "{}", game)?;
+	}
+	Ok(())
+}
+
+impl Hand {
+	pub fn parse_pbn(s: &str) -> Result<Hand> {
+		let holdings: Vec<&str> = s.split('.').collect();
+		if holdings.len() != 4 {
+			return Err(Error::parse(format!(
+				"PBN hand '{}' must have four suits",
+				s
+			)));
+		}
+		let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+		let mut cards = Vec::new();
+		for (suit, ranks) in suits.iter().zip(holdings) {
+			cards.extend(notation::english().parse_holding(*suit, ranks)?);
+		}
+		Hand::checked(cards)
+	}
+
+	pub fn to_pbn(&self) -> String {
+		[Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+			.iter()
+			.map(|suit| format!("{}", self.suit_holding(*suit)))
+			.collect::<Vec<String>>()
+			.join(".")
+	}
+}
+
+impl Deal {
+	pub fn parse_pbn(s: &str, dealer: Seat, vulnerable: Vulnerability) -> Result<Deal> {
+		let s = s.trim();
+		if s.get(1..2) != Some(":") {
+			return Err(Error::parse(format!(
+				"PBN deal '{}' must start with a seat",
+				s
+			)));
+		}
+		let first = Seat::parse_pbn(&s[0..1])?;
+		let hands = s[2..]
+			.split_whitespace()
+			.map(Hand::parse_pbn)
+			.collect::<Result<Vec<Hand>>>()?;
+		if hands.len() != 4 {
+			return Err(Error::parse(format!(
+				"PBN deal '{}' must have four hands",
+				s
+			)));
+		}
+
+		let mut builder = Deal::builder()
+			.with_dealer(dealer)
+			.with_vulnerability(vulnerable);
+		for (i, hand) in hands.into_iter().enumerate() {
+			builder = builder.with_hand(first.after(i), hand);
+		}
+		builder.build()
+	}
+
+	pub fn to_pbn(&self) -> String {
+		format!(
+			"N:{} {} {} {}",
+			self.north.to_pbn(),
+			self.east.to_pbn(),
+			self.south.to_pbn(),
+			self.west.to_pbn()
+		)
+	}
+}
+
+impl Seat {
+	pub fn parse_pbn(s: &str) -> Result<Seat> {
+		use self::Seat::*;
+		match s {
+			"N" => Ok(North),
+			"E" => Ok(East),
+			"S" => Ok(South),
+			"W" => Ok(West),
+			_ => Err(Error::parse(format!("invalid PBN seat '{}'", s))),
+		}
+	}
+
+	pub fn to_pbn(self) -> &'static str {
+		use self::Seat::*;
+		match self {
+			North => "N",
+			East => "E",
+			South => "S",
+			West => "W",
+		}
+	}
+}
+
+impl Vulnerability {
+	pub fn parse_pbn(s: &str) -> Result<Vulnerability> {
+		use self::Vulnerability::*;
+		match s {
+			"None" | "Love" | "-" => Ok(Neither),
+			"NS" => Ok(NS),
+			"EW" => Ok(EW),
+			"All" | "Both" => Ok(Both),
+			_ => Err(Error::parse(format!("invalid PBN vulnerability '{}'", s))),
+		}
+	}
+
+	pub fn to_pbn(self) -> &'static str {
+		use self::Vulnerability::*;
+		match self {
+			Neither => "None",
+			NS => "NS",
+			EW => "EW",
+			Both => "All",
+		}
+	}
+}
+
+fn bid_to_pbn(bid: Bid) -> String {
+	match bid {
+		Bid::Pass => "Pass".to_string(),
+		Bid::Double => "X".to_string(),
+		Bid::Redouble => "XX".to_string(),
+		Bid::Contract(c) => format!("{}", c),
+	}
+}
+
+fn bid_from_pbn(s: &str) -> Result<Bid> {
+	match s {
+		"Pass" => Ok(Bid::Pass),
+		"X" => Ok(Bid::Double),
+		"XX" => Ok(Bid::Redouble),
+		_ => Bid::parse(s),
+	}
+}
+
+// parses the tokens of an auction section into a bid sequence, with calls
+// suffixed by `!` alerted, along with (call index, note number) pairs for each
+// note reference
+fn parse_auction(tokens: &[String]) -> Result<(BidSequence, Vec<(usize, String)>)> {
+	let mut bids = BidSequence::empty();
+	let mut refs = Vec::new();
+	for token in tokens {
+		if token == "*" {
+			break;
+		} else if token == "-" || token.starts_with('$') {
+			continue;
+		} else if token.len() > 2 && token.starts_with('=') && token.ends_with('=') {
+			if bids.bids().is_empty() {
+				return Err(Error::parse("note reference before first call"));
+			}
+			refs.push((bids.bids().len() - 1, token[1..token.len() - 1].to_string()));
+		} else if token == "AP" {
+			while !bids.is_finished() {
+				bids = bids.with_continuation(Bid::Pass)?;
+			}
+		} else {
+			let call = token.trim_end_matches(&['!', '?'][..]);
+			let annotation = match token.contains('!') {
+				true => Annotation::alert(),
+				false => Annotation::default(),
+			};
+			bids = bids.with_annotated_continuation(bid_from_pbn(call)?, annotation)?;
+		}
+	}
+	Ok((bids, refs))
+}
+
+struct Section {
+	tag: Tag,
+	tokens: Vec<String>,
+}
+
+// splits the text of a single game into tags, each followed by the tokens
+// of its section (if any), skipping commentary
+fn tokenize(s: &str) -> Result<Vec<Section>> {
+	let mut sections: Vec<Section> = Vec::new();
+	let mut chars = s.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'[' => {
+				let mut name = String::new();
+				while let Some(&c) = chars.peek() {
+					if c.is_whitespace() || c == '"' {
+						break;
+					}
+					name.push(c);
+					chars.next();
+				}
+				while chars.peek().is_some_and(|c| c.is_whitespace()) {
+					chars.next();
+				}
+				if chars.next() != Some('"') {
+					return Err(Error::parse(format!("tag '{}' has no value", name)));
+				}
+				let mut value = String::new();
+				loop {
+					match chars.next() {
+						Some('\\') => value.extend(chars.next()),
+						Some('"') => break,
+						Some(c) => value.push(c),
+						None => {
+							return Err(Error::parse(format!(
+								"unterminated value for tag '{}'",
+								name
+							)))
+						}
+					}
+				}
+				while chars.peek().is_some_and(|c| c.is_whitespace()) {
+					chars.next();
+				}
+				if chars.next() != Some(']') {
+					return Err(Error::parse(format!("unterminated tag '{}'", name)));
+				}
+				sections.push(Section {
+					tag: Tag::new(name, value),
+					tokens: Vec::new(),
+				});
+			}
+			'{' => {
+				if !chars.any(|c| c == '}') {
+					return Err(Error::parse("unterminated commentary"));
+				}
+			}
+			';' => {
+				while chars.peek().is_some_and(|c| *c != '\n') {
+					chars.next();
+				}
+			}
+			c if c.is_whitespace() => (),
+			c => {
+				let mut token = c.to_string();
+				while let Some(&c) = chars.peek() {
+					if c.is_whitespace() || c == '[' || c == '{' || c == ';' {
+						break;
+					}
+					token.push(c);
+					chars.next();
+				}
+				match sections.last_mut() {
+					Some(section) => section.tokens.push(token),
+					None => return Err(Error::parse(format!("unexpected token '{}'", token))),
+				}
+			}
+		}
+	}
+	Ok(sections)
+}
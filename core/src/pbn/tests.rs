@@ -0,0 +1,216 @@
+use super::{parse, Game, Reader, Tag};
+use crate::{
+	auction::Auction, Annotation, Bid, BidSequence, Deal, Error, Hand, Seat, Vulnerability,
+};
+
+const DEAL: &str = "N:AKQ.JT9.876.5432 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8";
+
+const GAMES: &str = r##"% PBN 2.1
+[Event "Club Night"]
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQ.JT9.876.5432 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8"]
+[Auction "N"]
+1NT Pass 2C =1= Pass
+2D Pass 3NT AP
+[Note "1:Stayman"]
+
+{ a comment
+  spanning blank
+
+  lines }
+[Event "#"]
+[Board "2"]
+[Dealer "E"]
+[Vulnerable "All"]
+[Deal "E:AKQ.JT9.876.5432 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8"]
+"##;
+
+macro_rules! test_vulnerability_pbn {
+    ($($name:ident: $value:expr,)*) => {
+        mod vulnerability_pbn {
+            use super::super::super::Vulnerability::{self, *};
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $value;
+                assert_eq!(Vulnerability::parse_pbn(input).unwrap(), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_vulnerability_pbn! {
+	none: ("None", Neither),
+	love: ("Love", Neither),
+	dash: ("-", Neither),
+	ns: ("NS", NS),
+	ew: ("EW", EW),
+	all: ("All", Both),
+	both: ("Both", Both),
+}
+
+#[test]
+fn hand_round_trip() {
+	let hand = Hand::parse_pbn("AKQ..T98765.5432").unwrap();
+	assert_eq!(hand.hcp(), 9);
+	assert_eq!(hand.distribution().hearts, 0);
+	assert_eq!(hand.to_pbn(), "AKQ..T98765.5432");
+}
+
+#[test]
+fn hand_requires_four_suits() {
+	assert!(Hand::parse_pbn("AKQ.JT9.876").is_err());
+}
+
+#[test]
+fn deal_rotates_from_first_seat() {
+	let deal = Deal::parse_pbn(DEAL, Seat::West, Vulnerability::EW).unwrap();
+	let rotated = "S:765.8765.AKQ.JT9 432.432.JT9.AKQ8 AKQ.JT9.876.5432 JT98.AKQ.5432.76";
+	let other = Deal::parse_pbn(rotated, Seat::West, Vulnerability::EW).unwrap();
+	assert_eq!(deal, other);
+	assert_eq!(deal.north.to_pbn(), "AKQ.JT9.876.5432");
+	assert_eq!(deal.to_pbn(), DEAL);
+}
+
+#[test]
+fn deal_rejects_duplicates() {
+	let dup = "N:AKQ.JT9.876.5432 AKQ.JT9.876.5432 765.8765.AKQ.JT9 432.432.JT9.AKQ8";
+	assert!(Deal::parse_pbn(dup, Seat::North, Vulnerability::Neither).is_err());
+}
+
+#[test]
+fn deal_rejects_short_hands() {
+	let short = "N:AKQ.JT9.876.543 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8";
+	assert!(Deal::parse_pbn(short, Seat::North, Vulnerability::Neither).is_err());
+}
+
+#[test]
+fn deal_rejects_a_first_seat_that_is_not_ascii() {
+	for text in ["é:AKQ.JT9.876.5432", "Né AKQ.JT9.876.5432", "é", ""] {
+		match Deal::parse_pbn(text, Seat::North, Vulnerability::Neither) {
+			Err(Error::Parse(_)) => {}
+			other => panic!("expected a parse error for '{}', got {:?}", text, other),
+		}
+	}
+}
+
+#[test]
+fn reads_multiple_games() {
+	let games = parse(GAMES).unwrap();
+	assert_eq!(games.len(), 2);
+
+	let first = &games[0];
+	assert_eq!(first.tag("Board"), Some("1"));
+	assert_eq!(first.deal.dealer, Seat::North);
+	let auction = first.auction.as_ref().unwrap();
+	assert_eq!(
+		format!("{}", auction.bids()),
+		"1NT,Pass,2C,Pass,2D,Pass,3NT,Pass,Pass,Pass"
+	);
+	assert!(auction.is_finished());
+	assert_eq!(auction.dealer(), Seat::North);
+	assert_eq!(auction.seat_of(2), Some(Seat::South));
+	assert_eq!(
+		auction.annotation(2),
+		Some(&Annotation::explained(false, "Stayman"))
+	);
+
+	let second = &games[1];
+	assert_eq!(second.tag("Event"), Some("Club Night"));
+	assert_eq!(second.deal.dealer, Seat::East);
+	assert_eq!(second.deal.vulnerable, Vulnerability::Both);
+	assert_eq!(second.deal.east, first.deal.north);
+	assert!(second.auction.is_none());
+}
+
+#[test]
+fn write_round_trip() {
+	let games = parse(GAMES).unwrap();
+	for game in games {
+		let written = format!("{}", game);
+		assert_eq!(Game::parse(&written).unwrap(), game);
+	}
+}
+
+#[test]
+fn writes_the_roster_in_export_order() {
+	let mut game = Game::new(Deal::parse_pbn(DEAL, Seat::North, Vulnerability::Neither).unwrap());
+	game.tags = vec![
+		Tag::new("South", "Dee"),
+		Tag::new("Annotator", "Ann"),
+		Tag::new("Board", "3"),
+		Tag::new("Round", "2"),
+		Tag::new("Event", "Club Night"),
+	];
+	let written = format!("{}", game);
+	let names: Vec<&str> = written
+		.lines()
+		.filter_map(|line| line.strip_prefix('['))
+		.filter_map(|line| line.split(' ').next())
+		.collect();
+	assert_eq!(
+		names,
+		[
+			"Event",
+			"Round",
+			"Board",
+			"South",
+			"Dealer",
+			"Vulnerable",
+			"Deal",
+			"Annotator"
+		]
+	);
+}
+
+#[test]
+fn writes_notes_by_number() {
+	let deal = Deal::parse_pbn(DEAL, Seat::South, Vulnerability::NS).unwrap();
+	let mut game = Game::new(deal);
+	let mut auction = BidSequence::new(vec![
+		Bid::parse("1NT").unwrap(),
+		Bid::Pass,
+		Bid::parse("2H").unwrap(),
+		Bid::Double,
+	]);
+	auction
+		.annotate(2, Annotation::explained(true, "transfer"))
+		.unwrap();
+	game.auction = Some(Auction::from_bids(Seat::South, Vulnerability::NS, auction));
+	let written = format!("{}", game);
+	assert!(written.contains("[Auction \"S\"]\n1NT Pass 2H! =1= X\n[Note \"1:transfer\"]\n"));
+	assert_eq!(Game::parse(&written).unwrap(), game);
+}
+
+#[test]
+fn auction_starts_with_its_tag() {
+	let text = format!("[Deal \"{}\"]\n[Auction \"W\"]\nPass 1NT\n", DEAL);
+	let game = Game::parse(&text).unwrap();
+	let auction = game.auction.unwrap();
+	assert_eq!(auction.dealer(), Seat::West);
+	assert_eq!(auction.opener(), Some(Seat::North));
+	assert_eq!(auction.next_seat(), Seat::East);
+}
+
+#[test]
+fn rejects_missing_note() {
+	let text = format!("[Deal \"{}\"]\n[Auction \"N\"]\n1NT =1= Pass\n", DEAL);
+	assert!(Game::parse(&text).is_err());
+}
+
+#[test]
+fn rejects_illegal_auction() {
+	let text = format!("[Deal \"{}\"]\n[Auction \"N\"]\n1NT 1S\n", DEAL);
+	assert!(Game::parse(&text).is_err());
+}
+
+#[test]
+fn reader_over_buffered_input() {
+	let games: Vec<Game> = Reader::new(std::io::Cursor::new(GAMES))
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(games.len(), 2);
+}
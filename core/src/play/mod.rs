@@ -0,0 +1,176 @@
+//! The play of the cards: leads, following suit and winning tricks once the
+//! auction has settled the contract.
+
+use super::{
+	auction::Auction, dd::Tricks, AuctionResult, Card, Contract, Deal, Error, Hand, Result, Seat,
+	Trump,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trick {
+	pub leader: Seat,
+	// in the order played, starting with the lead
+	pub cards: Vec<Card>,
+	pub winner: Seat,
+}
+
+#[derive(Debug, Clone)]
+pub struct Play {
+	contract: Contract,
+	declarer: Seat,
+	// each seat's unplayed cards, by seat index
+	hands: Vec<Hand>,
+	tricks: Vec<Trick>,
+	leader: Seat,
+	current: Vec<Card>,
+}
+
+impl Play {
+	// the play after the deal's auction, which must start with its dealer
+	pub fn new(deal: &Deal, auction: &Auction) -> Result<Self> {
+		if auction.dealer() != deal.dealer {
+			return Err(Error::bridge(format!(
+				"the auction starts with {} but {} dealt",
+				auction.dealer(),
+				deal.dealer
+			)));
+		}
+		match auction.final_contract()? {
+			AuctionResult::Contract(c) => Ok(Self::with_contract(deal, c.contract, c.declarer)),
+			AuctionResult::PassedOut => {
+				Err(Error::bridge("there is no play after a passed out auction"))
+			}
+		}
+	}
+
+	pub fn with_contract(deal: &Deal, contract: Contract, declarer: Seat) -> Self {
+		let hands = Seat::vec()
+			.into_iter()
+			.map(|seat| deal.hand_for_seat(seat).clone())
+			.collect();
+		Play {
+			contract,
+			declarer,
+			hands,
+			tricks: Vec::new(),
+			leader: declarer.lho(),
+			current: Vec::new(),
+		}
+	}
+
+	pub fn contract(&self) -> Contract {
+		self.contract
+	}
+
+	pub fn trump(&self) -> Trump {
+		self.contract.1
+	}
+
+	pub fn declarer(&self) -> Seat {
+		self.declarer
+	}
+
+	pub fn dummy(&self) -> Seat {
+		self.declarer.partner()
+	}
+
+	// the seat to play next, or none once all thirteen tricks are played
+	pub fn next_seat(&self) -> Option<Seat> {
+		if self.is_finished() {
+			return None;
+		}
+		Some(self.leader.after(self.current.len()))
+	}
+
+	pub fn remaining(&self, seat: Seat) -> &Hand {
+		&self.hands[seat.index()]
+	}
+
+	// the cards the next seat may play, which must follow the suit led when
+	// they can
+	pub fn legal_cards(&self) -> Vec<Card> {
+		let seat = match self.next_seat() {
+			Some(seat) => seat,
+			None => return Vec::new(),
+		};
+		let hand = self.hands[seat.index()].cards();
+		match self.current.first() {
+			Some(lead) if hand.suit_length(lead.suit) > 0 => hand.suit(lead.suit).iter().collect(),
+			_ => hand.iter().collect(),
+		}
+	}
+
+	pub fn play(&mut self, card: Card) -> Result<()> {
+		let seat = match self.next_seat() {
+			Some(seat) => seat,
+			None => return Err(Error::bridge("all tricks have been played")),
+		};
+		if !self.hands[seat.index()].contains(card) {
+			return Err(Error::bridge(format!("{} does not hold {}", seat, card)));
+		}
+		if !self.legal_cards().contains(&card) {
+			return Err(Error::bridge(format!("{} must follow suit", seat)));
+		}
+		self.hands[seat.index()].0.remove(card);
+		self.current.push(card);
+		if self.current.len() == 4 {
+			let winner = self.winner();
+			self.tricks.push(Trick {
+				leader: self.leader,
+				cards: self.current.drain(..).collect(),
+				winner,
+			});
+			self.leader = winner;
+		}
+		Ok(())
+	}
+
+	// the cards played so far to the trick in progress, starting with the lead
+	pub fn current_trick(&self) -> &[Card] {
+		&self.current
+	}
+
+	pub fn tricks(&self) -> &[Trick] {
+		&self.tricks
+	}
+
+	pub fn tricks_won(&self) -> Tricks {
+		let ns = self
+			.tricks
+			.iter()
+			.filter(|t| t.winner == Seat::North || t.winner == Seat::South)
+			.count();
+		Tricks {
+			ns,
+			ew: self.tricks.len() - ns,
+		}
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.tricks.len() == 13
+	}
+
+	// the winner of the complete trick in progress
+	fn winner(&self) -> Seat {
+		let trump = match self.trump() {
+			Trump::Trump(suit) => Some(suit),
+			Trump::NoTrump => None,
+		};
+		let mut best = 0;
+		for (idx, card) in self.current.iter().enumerate().skip(1) {
+			let current = self.current[best];
+			let beats = if card.suit == current.suit {
+				card.rank > current.rank
+			} else {
+				Some(card.suit) == trump
+			};
+			if beats {
+				best = idx;
+			}
+		}
+		self.leader.after(best)
+	}
+}
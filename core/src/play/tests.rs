@@ -0,0 +1,126 @@
+use super::Play;
+use crate::{auction::Auction, Card, Contract, Deal, Rank, Seat, Suit, Vulnerability};
+
+// north: AKQ.JT9.876.5432
+// east: JT98.AKQ.5432.76
+// south: 765.8765.AKQ.JT9
+// west: 432.432.JT9.AKQ8
+fn deal(dealer: Seat) -> Deal {
+	Deal::parse_pbn(
+		"N:AKQ.JT9.876.5432 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8",
+		dealer,
+		Vulnerability::Neither,
+	)
+	.unwrap()
+}
+
+fn card(s: &str) -> Card {
+	let suit = match &s[0..1] {
+		"S" => Suit::Spades,
+		"H" => Suit::Hearts,
+		"D" => Suit::Diamonds,
+		_ => Suit::Clubs,
+	};
+	Card {
+		suit,
+		rank: Rank::parse(&s[1..]).unwrap(),
+	}
+}
+
+fn play(contract: &str, declarer: Seat) -> Play {
+	Play::with_contract(
+		&deal(Seat::North),
+		Contract::parse(contract).unwrap(),
+		declarer,
+	)
+}
+
+fn auction(calls: &str, dealer: Seat) -> Auction {
+	Auction::parse(calls, dealer, Vulnerability::Neither).unwrap()
+}
+
+#[test]
+fn declarer_from_auction() {
+	let calls = "1C,Pass,1S,Pass,2S,Pass,4S,Pass,Pass,Pass";
+	let play = Play::new(&deal(Seat::North), &auction(calls, Seat::North)).unwrap();
+	assert_eq!(play.declarer(), Seat::South);
+	assert_eq!(play.dummy(), Seat::North);
+	assert_eq!(play.contract(), Contract::parse("4S").unwrap());
+	assert_eq!(play.next_seat(), Some(Seat::West));
+
+	// the opener's partner bid the suit first, though the opener raised it
+	let play = Play::new(&deal(Seat::East), &auction(calls, Seat::East)).unwrap();
+	assert_eq!(play.declarer(), Seat::West);
+}
+
+#[test]
+fn declarer_after_doubles() {
+	let auction = auction("1H,Dbl,2H,Pass,Pass,Dbl,Pass,Pass,Pass", Seat::West);
+	let play = Play::new(&deal(Seat::West), &auction).unwrap();
+	assert_eq!(play.declarer(), Seat::West);
+}
+
+#[test]
+fn rejects_an_auction_from_another_dealer() {
+	let calls = "1C,Pass,1S,Pass,2S,Pass,4S,Pass,Pass,Pass";
+	assert!(Play::new(&deal(Seat::North), &auction(calls, Seat::East)).is_err());
+}
+
+#[test]
+fn rejects_unfinished_and_passed_out_auctions() {
+	let unfinished = auction("1NT,Pass", Seat::North);
+	assert!(Play::new(&deal(Seat::North), &unfinished).is_err());
+	let passed_out = auction("Pass,Pass,Pass,Pass", Seat::North);
+	assert!(Play::new(&deal(Seat::North), &passed_out).is_err());
+}
+
+#[test]
+fn must_follow_suit() {
+	let mut play = play("3NT", Seat::South);
+	assert!(play.play(card("SA")).is_err());
+	play.play(card("C8")).unwrap();
+	assert_eq!(play.legal_cards().len(), 4);
+	assert!(play.play(card("SA")).is_err());
+	play.play(card("C2")).unwrap();
+	// east has two clubs
+	assert_eq!(play.legal_cards(), vec![card("C7"), card("C6")]);
+	play.play(card("C7")).unwrap();
+	play.play(card("CJ")).unwrap();
+	assert_eq!(play.tricks()[0].winner, Seat::South);
+	assert_eq!(play.next_seat(), Some(Seat::South));
+	assert_eq!(play.remaining(Seat::East).0.len(), 12);
+}
+
+#[test]
+fn trumps_win_tricks() {
+	let mut play = play("4H", Seat::West);
+	for c in &["SA", "S8", "S5", "S2"] {
+		play.play(card(c)).unwrap();
+	}
+	assert_eq!(play.tricks()[0].winner, Seat::North);
+	for c in &["C5", "C6", "C9", "CA", "CK", "C4", "C7", "CT", "CQ"] {
+		play.play(card(c)).unwrap();
+	}
+	assert!(play.play(card("SK")).is_err());
+	play.play(card("C3")).unwrap();
+	// east ruffs the third round of clubs
+	play.play(card("HK")).unwrap();
+	play.play(card("CJ")).unwrap();
+	assert_eq!(play.tricks()[3].winner, Seat::East);
+	assert_eq!(play.tricks_won().ns, 1);
+	assert_eq!(play.tricks_won().ew, 3);
+}
+
+#[test]
+fn plays_all_thirteen_tricks() {
+	let mut play = play("7NT", Seat::South);
+	while let Some(card) = play.legal_cards().first().cloned() {
+		play.play(card).unwrap();
+	}
+	assert!(play.is_finished());
+	assert_eq!(play.next_seat(), None);
+	assert_eq!(play.tricks().len(), 13);
+	let won = play.tricks_won();
+	assert_eq!(won.ns + won.ew, 13);
+	assert!(play.play(card("SA")).is_err());
+}
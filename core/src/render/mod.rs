@@ -0,0 +1,419 @@
+//! Diagrams of hands, deals and auctions for handouts, web pages and the
+//! terminal: single hands, the compass diagram of a whole deal and the
+//! auction table below it.
+//!
+//! Plain text writes suits as letters and is safe for any terminal. Unicode
+//! uses the suit symbols. HTML and LaTeX give fragments to place in a page or
+//! document, with classes on the HTML elements for styling; the LaTeX needs no
+//! packages. SVG gives a complete image of the Unicode diagram.
+
+use super::{auction::Auction, Bid, Deal, Error, Hand, Result, Seat, Suit, Trump};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+// the width of each hand in a text diagram, enough for a thirteen-card suit
+const HAND_WIDTH: usize = 16;
+
+// the width of each column in a text auction table
+const CALL_WIDTH: usize = 6;
+
+// the size of a character cell in an SVG diagram
+const SVG_CHAR_WIDTH: usize = 9;
+const SVG_LINE_HEIGHT: usize = 18;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+	Text,
+	Unicode,
+	Html,
+	Svg,
+	Latex,
+}
+
+impl Format {
+	pub fn parse(s: &str) -> Result<Self> {
+		match s {
+			"text" => Ok(Format::Text),
+			"unicode" => Ok(Format::Unicode),
+			"html" => Ok(Format::Html),
+			"svg" => Ok(Format::Svg),
+			"latex" => Ok(Format::Latex),
+			_ => Err(Error::parse(format!("unknown format '{}'", s))),
+		}
+	}
+
+	fn suit(self, suit: Suit) -> String {
+		let red = suit == Suit::Hearts || suit == Suit::Diamonds;
+		match self {
+			Format::Text => suit.to_string(),
+			Format::Unicode | Format::Svg => symbol(suit).to_string(),
+			Format::Html => {
+				let class = if red { "suit red" } else { "suit black" };
+				format!("<span class=\"{}\">{}</span>", class, symbol(suit))
+			}
+			Format::Latex => {
+				let name = match suit {
+					Suit::Spades => "spadesuit",
+					Suit::Hearts => "heartsuit",
+					Suit::Diamonds => "diamondsuit",
+					Suit::Clubs => "clubsuit",
+				};
+				format!("$\\{}$", name)
+			}
+		}
+	}
+
+	// the mark for a void
+	fn void(self) -> &'static str {
+		match self {
+			Format::Text => "-",
+			Format::Unicode | Format::Svg | Format::Html => "\u{2014}",
+			Format::Latex => "---",
+		}
+	}
+
+	fn escape(self, s: &str) -> String {
+		match self {
+			// the lines of an image are escaped as they are drawn
+			Format::Text | Format::Unicode | Format::Svg => s.to_string(),
+			Format::Html => s
+				.replace('&', "&amp;")
+				.replace('<', "&lt;")
+				.replace('>', "&gt;")
+				.replace('"', "&quot;"),
+			Format::Latex => s
+				.chars()
+				.map(|c| match c {
+					'\\' => "\\textbackslash{}".to_string(),
+					'~' => "\\textasciitilde{}".to_string(),
+					'^' => "\\textasciicircum{}".to_string(),
+					'&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{}", c),
+					c => c.to_string(),
+				})
+				.collect(),
+		}
+	}
+
+	// a call as written in an auction, with a `!` if it is alerted
+	fn call(self, bid: Bid, alert: bool) -> String {
+		let call = match bid {
+			Bid::Contract(contract) => match contract.1 {
+				Trump::NoTrump => format!("{}NT", contract.0),
+				Trump::Trump(suit) => format!("{}{}", contract.0, self.suit(suit)),
+			},
+			bid => bid.to_string(),
+		};
+		if alert {
+			format!("{}!", call)
+		} else {
+			call
+		}
+	}
+}
+
+impl fmt::Display for Format {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let s = match self {
+			Format::Text => "text",
+			Format::Unicode => "unicode",
+			Format::Html => "html",
+			Format::Svg => "svg",
+			Format::Latex => "latex",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+fn symbol(suit: Suit) -> char {
+	match suit {
+		Suit::Spades => '\u{2660}',
+		Suit::Hearts => '\u{2665}',
+		Suit::Diamonds => '\u{2666}',
+		Suit::Clubs => '\u{2663}',
+	}
+}
+
+// the suit symbol and cards of each suit, spades first
+fn suit_lines(hand: &Hand, format: Format) -> Vec<String> {
+	SUITS
+		.iter()
+		.map(|suit| {
+			let cards = hand.suit_holding(*suit).to_string();
+			let cards = if cards.is_empty() {
+				format.void().to_string()
+			} else {
+				cards
+			};
+			format!("{} {}", format.suit(*suit), cards)
+		})
+		.collect()
+}
+
+fn hand_lines(hand: &Hand, seat: Option<Seat>, format: Format) -> Vec<String> {
+	let mut lines: Vec<String> = seat.iter().map(|s| s.to_string()).collect();
+	lines.extend(suit_lines(hand, format));
+	lines
+}
+
+// the lines of the compass diagram, with dealer and vulnerability in the
+// top left corner
+fn deal_lines(deal: &Deal, format: Format) -> Vec<String> {
+	let block = |seat| hand_lines(deal.hand_for_seat(seat), Some(seat), format);
+	let corner = [
+		format!("Dealer: {}", deal.dealer),
+		format!("Vul: {}", deal.vulnerable),
+	];
+	let mut lines = Vec::new();
+	for (i, line) in block(Seat::North).iter().enumerate() {
+		let left = corner.get(i).map_or("", |s| s.as_str());
+		lines.push(format!("{:<w$}{}", left, line, w = HAND_WIDTH));
+	}
+	for (west, east) in block(Seat::West).iter().zip(block(Seat::East)) {
+		lines.push(format!("{:<w$}{}", west, east, w = 2 * HAND_WIDTH));
+	}
+	for line in block(Seat::South) {
+		lines.push(format!("{:<w$}{}", "", line, w = HAND_WIDTH));
+	}
+	lines
+}
+
+// the calls of the auction from north clockwise, with gaps before the
+// dealer's first call
+fn auction_rows(auction: &Auction, format: Format) -> Vec<Vec<String>> {
+	let mut calls = vec![String::new(); auction.dealer().index()];
+	for call in auction.calls() {
+		calls.push(format.call(call.bid, call.annotation.alert));
+	}
+	calls.chunks(4).map(|row| row.to_vec()).collect()
+}
+
+// the explanations of the calls, each with the seat that made the call
+fn explanations(auction: &Auction, format: Format) -> Vec<String> {
+	auction
+		.calls()
+		.filter_map(|call| {
+			let explanation = call.annotation.explanation.as_ref()?;
+			Some(format!(
+				"{} {}: {}",
+				call.seat,
+				format.call(call.bid, call.annotation.alert),
+				format.escape(explanation)
+			))
+		})
+		.collect()
+}
+
+fn auction_lines(auction: &Auction, format: Format) -> Vec<String> {
+	let header: String = Seat::vec()
+		.iter()
+		.map(|seat| format!("{:<w$}", seat.to_string(), w = CALL_WIDTH))
+		.collect();
+	let mut lines = vec![header.trim_end().to_string()];
+	for row in auction_rows(auction, format) {
+		let row: String = row
+			.iter()
+			.map(|call| format!("{:<w$}", call, w = CALL_WIDTH))
+			.collect();
+		lines.push(row.trim_end().to_string());
+	}
+	lines.extend(explanations(auction, format));
+	lines
+}
+
+fn text(lines: Vec<String>) -> String {
+	lines
+		.iter()
+		.map(|line| format!("{}\n", line.trim_end()))
+		.collect()
+}
+
+// the lines of text as an image, with red suit symbols
+fn svg(lines: Vec<String>) -> String {
+	let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) + 2;
+	let height = lines.len() + 1;
+	let mut out = format!(
+		"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"15\">\n",
+		width * SVG_CHAR_WIDTH,
+		height * SVG_LINE_HEIGHT
+	);
+	for (i, line) in lines.iter().enumerate() {
+		let line = line.trim_end();
+		if line.is_empty() {
+			continue;
+		}
+		let content: String = line
+			.chars()
+			.map(|c| match c {
+				'\u{2665}' | '\u{2666}' => format!("<tspan fill=\"red\">{}</tspan>", c),
+				c => Format::Html.escape(&c.to_string()),
+			})
+			.collect();
+		out.push_str(&format!(
+			"<text x=\"{}\" y=\"{}\" xml:space=\"preserve\">{}</text>\n",
+			SVG_CHAR_WIDTH,
+			(i + 1) * SVG_LINE_HEIGHT,
+			content
+		));
+	}
+	out.push_str("</svg>\n");
+	out
+}
+
+fn html_hand(hand: &Hand, seat: Option<Seat>) -> String {
+	let mut out = String::from("<div class=\"bridge-hand\">\n");
+	if let Some(seat) = seat {
+		out.push_str(&format!("<div class=\"seat\">{}</div>\n", seat));
+	}
+	for line in suit_lines(hand, Format::Html) {
+		out.push_str(&format!("<div>{}</div>\n", line));
+	}
+	out.push_str("</div>\n");
+	out
+}
+
+fn html_deal(deal: &Deal) -> String {
+	let hand = |seat| html_hand(deal.hand_for_seat(seat), Some(seat));
+	format!(
+		"<table class=\"bridge-deal\">\n\
+		 <tr><td>Dealer: {}<br>Vul: {}</td><td>\n{}</td><td></td></tr>\n\
+		 <tr><td>\n{}</td><td></td><td>\n{}</td></tr>\n\
+		 <tr><td></td><td>\n{}</td><td></td></tr>\n\
+		 </table>\n",
+		deal.dealer,
+		deal.vulnerable,
+		hand(Seat::North),
+		hand(Seat::West),
+		hand(Seat::East),
+		hand(Seat::South)
+	)
+}
+
+fn html_auction(auction: &Auction) -> String {
+	let mut out = String::from("<table class=\"bridge-auction\">\n<tr>");
+	for seat in Seat::vec() {
+		out.push_str(&format!("<th>{}</th>", seat));
+	}
+	out.push_str("</tr>\n");
+	for row in auction_rows(auction, Format::Html) {
+		out.push_str("<tr>");
+		for call in row {
+			out.push_str(&format!("<td>{}</td>", call));
+		}
+		out.push_str("</tr>\n");
+	}
+	out.push_str("</table>\n");
+	let explanations = explanations(auction, Format::Html);
+	if !explanations.is_empty() {
+		out.push_str("<ul class=\"bridge-explanations\">\n");
+		for explanation in explanations {
+			out.push_str(&format!("<li>{}</li>\n", explanation));
+		}
+		out.push_str("</ul>\n");
+	}
+	out
+}
+
+fn latex_hand(hand: &Hand, seat: Option<Seat>) -> String {
+	let lines = hand_lines(hand, seat, Format::Latex);
+	format!(
+		"\\begin{{tabular}}{{@{{}}l@{{}}}}\n{}\n\\end{{tabular}}",
+		lines.join(" \\\\\n")
+	)
+}
+
+fn latex_deal(deal: &Deal) -> String {
+	let hand = |seat| latex_hand(deal.hand_for_seat(seat), Some(seat));
+	format!(
+		"\\begin{{tabular}}{{lll}}\n\
+		 \\begin{{tabular}}{{@{{}}l@{{}}}}Dealer: {} \\\\ Vul: {}\\end{{tabular}} & {} & \\\\\n\
+		 {} & & {} \\\\\n\
+		 & {} & \\\\\n\
+		 \\end{{tabular}}\n",
+		deal.dealer,
+		deal.vulnerable,
+		hand(Seat::North),
+		hand(Seat::West),
+		hand(Seat::East),
+		hand(Seat::South)
+	)
+}
+
+fn latex_auction(auction: &Auction) -> String {
+	let header: Vec<String> = Seat::vec().iter().map(|s| s.to_string()).collect();
+	let mut out = format!(
+		"\\begin{{tabular}}{{llll}}\n{} \\\\\n\\hline\n",
+		header.join(" & ")
+	);
+	for mut row in auction_rows(auction, Format::Latex) {
+		row.resize(4, String::new());
+		out.push_str(&format!("{} \\\\\n", row.join(" & ")));
+	}
+	out.push_str("\\end{tabular}\n");
+	for explanation in explanations(auction, Format::Latex) {
+		out.push_str(&format!("\n{}\n", explanation));
+	}
+	out
+}
+
+// a hand on its own, one suit to a line
+pub fn hand(hand: &Hand, format: Format) -> String {
+	match format {
+		Format::Text | Format::Unicode => text(hand_lines(hand, None, format)),
+		Format::Svg => svg(hand_lines(hand, None, format)),
+		Format::Html => html_hand(hand, None),
+		Format::Latex => format!("{}\n", latex_hand(hand, None)),
+	}
+}
+
+// one player's hand in the deal, under the name of the seat
+pub fn seat(deal: &Deal, seat: Seat, format: Format) -> String {
+	let hand = deal.hand_for_seat(seat);
+	match format {
+		Format::Text | Format::Unicode => text(hand_lines(hand, Some(seat), format)),
+		Format::Svg => svg(hand_lines(hand, Some(seat), format)),
+		Format::Html => html_hand(hand, Some(seat)),
+		Format::Latex => format!("{}\n", latex_hand(hand, Some(seat))),
+	}
+}
+
+// all four hands around the table, north at the top
+pub fn deal(deal: &Deal, format: Format) -> String {
+	match format {
+		Format::Text | Format::Unicode => text(deal_lines(deal, format)),
+		Format::Svg => svg(deal_lines(deal, format)),
+		Format::Html => html_deal(deal),
+		Format::Latex => latex_deal(deal),
+	}
+}
+
+// the auction table, from north clockwise, followed by the explanations of
+// any explained calls
+pub fn auction(auction: &Auction, format: Format) -> String {
+	match format {
+		Format::Text | Format::Unicode => text(auction_lines(auction, format)),
+		Format::Svg => svg(auction_lines(auction, format)),
+		Format::Html => html_auction(auction),
+		Format::Latex => latex_auction(auction),
+	}
+}
+
+// the diagram of the deal with the table of its auction below
+pub fn deal_with_auction(deal: &Deal, auction: &Auction, format: Format) -> String {
+	match format {
+		Format::Text | Format::Unicode | Format::Svg => {
+			let mut lines = deal_lines(deal, format);
+			lines.push(String::new());
+			lines.extend(auction_lines(auction, format));
+			match format {
+				Format::Svg => svg(lines),
+				_ => text(lines),
+			}
+		}
+		Format::Html => format!("{}{}", html_deal(deal), html_auction(auction)),
+		Format::Latex => format!("{}\n{}", latex_deal(deal), latex_auction(auction)),
+	}
+}
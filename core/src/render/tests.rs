@@ -0,0 +1,111 @@
+use super::{auction, deal, deal_with_auction, hand, seat, Format};
+use crate::{auction::Auction, Annotation, Deal, Hand, Seat, Vulnerability};
+
+fn example() -> Deal {
+	Deal::parse_pbn(
+		"N:AKQ.JT9.876.5432 JT98.AKQ.5432.76 765.8765.AKQ.JT9 432.432.JT9.AKQ8",
+		Seat::East,
+		Vulnerability::NS,
+	)
+	.unwrap()
+}
+
+fn stayman() -> Auction {
+	let mut auction = Auction::parse("1NT,Pass,2C!,Pass", Seat::East, Vulnerability::NS).unwrap();
+	auction
+		.annotate(2, Annotation::explained(true, "Stayman & <4M>"))
+		.unwrap();
+	auction
+}
+
+#[test]
+fn text_deal() {
+	let expected = "\
+Dealer: East    North
+Vul: NS         S AKQ
+                H JT9
+                D 876
+                C 5432
+West                            East
+S 432                           S JT98
+H 432                           H AKQ
+D JT9                           D 5432
+C AKQ8                          C 76
+                South
+                S 765
+                H 8765
+                D AKQ
+                C JT9
+";
+	assert_eq!(deal(&example(), Format::Text), expected);
+}
+
+#[test]
+fn unicode_auction() {
+	let expected = "\
+North East  South West
+      1NT   Pass  2\u{2663}!
+Pass
+West 2\u{2663}!: Stayman & <4M>
+";
+	assert_eq!(auction(&stayman(), Format::Unicode), expected);
+}
+
+#[test]
+fn single_hands() {
+	let voids = Hand::parse("AKQJT98765432|||").unwrap();
+	assert_eq!(
+		hand(&voids, Format::Text),
+		"S AKQJT98765432\nH -\nD -\nC -\n"
+	);
+	assert_eq!(
+		seat(&example(), Seat::West, Format::Unicode),
+		"West\n\u{2660} 432\n\u{2665} 432\n\u{2666} JT9\n\u{2663} AKQ8\n"
+	);
+}
+
+#[test]
+fn html() {
+	let out = deal_with_auction(&example(), &stayman(), Format::Html);
+	assert!(out.starts_with("<table class=\"bridge-deal\">"));
+	assert!(out.contains("<div class=\"seat\">North</div>"));
+	assert!(out.contains("<div><span class=\"suit red\">\u{2665}</span> JT9</div>"));
+	assert!(out.contains("<td>2<span class=\"suit black\">\u{2663}</span>!</td>"));
+	assert!(out.contains("Stayman &amp; &lt;4M&gt;"));
+}
+
+#[test]
+fn svg() {
+	let out = deal_with_auction(&example(), &stayman(), Format::Svg);
+	assert!(out.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+	assert!(out.ends_with("</svg>\n"));
+	assert!(out.contains(">West                            East</text>"));
+	assert!(out.contains("<tspan fill=\"red\">\u{2665}</tspan> AKQ"));
+	assert!(out.contains("Stayman &amp; &lt;4M&gt;</text>"));
+}
+
+#[test]
+fn latex() {
+	let out = deal_with_auction(&example(), &stayman(), Format::Latex);
+	assert!(out.contains("$\\spadesuit$ AKQ \\\\\n$\\heartsuit$ JT9"));
+	assert!(out.contains(" & 1NT & Pass & 2$\\clubsuit$! \\\\\n"));
+	assert!(out.contains("Stayman \\& <4M>"));
+	assert_eq!(
+		out.matches("\\begin{tabular}").count(),
+		out.matches("\\end{tabular}").count()
+	);
+}
+
+#[test]
+fn parses_formats() {
+	for format in &[
+		Format::Text,
+		Format::Unicode,
+		Format::Html,
+		Format::Svg,
+		Format::Latex,
+	] {
+		assert_eq!(Format::parse(&format.to_string()).unwrap(), *format);
+	}
+	assert!(Format::parse("pdf").is_err());
+}
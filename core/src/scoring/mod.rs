@@ -0,0 +1,215 @@
+//! Duplicate scoring of a played contract, and IMP and matchpoint comparisons
+//! between results on the same board.
+
+use super::{Contract, Seat, Suit, Trump, Vulnerability};
+use serde::{Deserialize, Serialize};
+use std::{cmp, fmt};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Doubling {
+	Undoubled,
+	Doubled,
+	Redoubled,
+}
+
+impl Doubling {
+	fn multiplier(self) -> i32 {
+		match self {
+			Doubling::Undoubled => 1,
+			Doubling::Doubled => 2,
+			Doubling::Redoubled => 4,
+		}
+	}
+}
+
+impl fmt::Display for Doubling {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let s = match self {
+			Doubling::Undoubled => "",
+			Doubling::Doubled => "x",
+			Doubling::Redoubled => "xx",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+// the two sides' scores for a board, which always sum to zero
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+	pub ns: i32,
+	pub ew: i32,
+}
+
+impl Score {
+	pub fn new(
+		contract: Contract,
+		doubling: Doubling,
+		declarer: Seat,
+		tricks: usize,
+		vulnerable: Vulnerability,
+	) -> Self {
+		let side_vulnerable = vulnerable.is_vulnerable(declarer.side());
+		let score = declarer_score(contract, doubling, tricks, side_vulnerable);
+		match declarer {
+			Seat::North | Seat::South => Score {
+				ns: score,
+				ew: -score,
+			},
+			Seat::East | Seat::West => Score {
+				ns: -score,
+				ew: score,
+			},
+		}
+	}
+
+	pub fn passed_out() -> Self {
+		Score { ns: 0, ew: 0 }
+	}
+
+	pub fn for_seat(&self, seat: Seat) -> i32 {
+		match seat {
+			Seat::North | Seat::South => self.ns,
+			Seat::East | Seat::West => self.ew,
+		}
+	}
+}
+
+// the declaring side's score for a contract taking `tricks` tricks
+pub fn declarer_score(
+	contract: Contract,
+	doubling: Doubling,
+	tricks: usize,
+	vulnerable: bool,
+) -> i32 {
+	let Contract(level, trump) = contract;
+	let level = level.number();
+	let needed = level + 6;
+	if tricks < needed {
+		return -undertricks(needed - tricks, doubling, vulnerable);
+	}
+
+	let per_trick = match trump {
+		Trump::Trump(Suit::Clubs) | Trump::Trump(Suit::Diamonds) => 20,
+		_ => 30,
+	};
+	let first_trick = if trump == Trump::NoTrump { 10 } else { 0 };
+	let multiplier = doubling.multiplier();
+	let contract_points = (per_trick * level as i32 + first_trick) * multiplier;
+
+	let overtricks = (tricks - needed) as i32;
+	let overtrick_value = match (doubling, vulnerable) {
+		(Doubling::Undoubled, _) => per_trick,
+		(_, false) => 50 * multiplier,
+		(_, true) => 100 * multiplier,
+	};
+
+	let game = match (contract_points >= 100, vulnerable) {
+		(false, _) => 50,
+		(true, false) => 300,
+		(true, true) => 500,
+	};
+	let slam = match (level, vulnerable) {
+		(6, false) => 500,
+		(6, true) => 750,
+		(7, false) => 1000,
+		(7, true) => 1500,
+		_ => 0,
+	};
+	// for making a doubled contract
+	let insult = match doubling {
+		Doubling::Undoubled => 0,
+		Doubling::Doubled => 50,
+		Doubling::Redoubled => 100,
+	};
+	contract_points + overtricks * overtrick_value + game + slam + insult
+}
+
+// the penalty for going `down` tricks
+fn undertricks(down: usize, doubling: Doubling, vulnerable: bool) -> i32 {
+	let down = down as i32;
+	let penalty = match (doubling, vulnerable) {
+		(Doubling::Undoubled, false) => return 50 * down,
+		(Doubling::Undoubled, true) => return 100 * down,
+		// 100, 300, 500 and then 300 a trick
+		(_, false) => match down {
+			1 => 100,
+			2 => 300,
+			n => 500 + 300 * (n - 3),
+		},
+		// 200 and then 300 a trick
+		(_, true) => 200 + 300 * (down - 1),
+	};
+	penalty * doubling.multiplier() / 2
+}
+
+// the lowest score difference worth each number of IMPs, from one up
+const IMP_TABLE: [i32; 24] = [
+	20, 50, 90, 130, 170, 220, 270, 320, 370, 430, 500, 600, 750, 900, 1100, 1300, 1500, 1750,
+	2000, 2250, 2500, 3000, 3500, 4000,
+];
+
+// the IMPs for a difference between two scores on the same board, negative
+// when the difference is
+pub fn imps(difference: i32) -> i32 {
+	let imps = IMP_TABLE
+		.iter()
+		.take_while(|min| difference.abs() >= **min)
+		.count() as i32;
+	imps * difference.signum()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Matchpoints {
+	pub points: f64,
+	pub top: f64,
+}
+
+impl Matchpoints {
+	// one point for every result in the field beaten and half for every tie,
+	// with scores from the same side's point of view
+	pub fn new(score: i32, field: &[i32]) -> Self {
+		let points = field
+			.iter()
+			.map(|other| match score.cmp(other) {
+				cmp::Ordering::Greater => 1.0,
+				cmp::Ordering::Equal => 0.5,
+				cmp::Ordering::Less => 0.0,
+			})
+			.sum();
+		Matchpoints {
+			points,
+			top: field.len() as f64,
+		}
+	}
+
+	// the other side's matchpoints on the same comparisons
+	pub fn opponents(&self) -> Self {
+		Matchpoints {
+			points: self.top - self.points,
+			top: self.top,
+		}
+	}
+
+	// scales the points up to a field of `results` scores, including this one,
+	// when fewer were played so that every board carries the same top
+	pub fn neuberg(&self, results: usize) -> Self {
+		let played = self.top + 1.0;
+		let results = results as f64;
+		Matchpoints {
+			points: (self.points + 0.5) * results / played - 0.5,
+			top: results - 1.0,
+		}
+	}
+
+	pub fn percentage(&self) -> f64 {
+		if self.top == 0.0 {
+			// a result with nothing to compare to is average
+			50.0
+		} else {
+			100.0 * self.points / self.top
+		}
+	}
+}
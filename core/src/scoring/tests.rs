@@ -0,0 +1,171 @@
+use super::{imps, Doubling, Matchpoints, Score};
+use crate::{dd::Table, par::Par, Contract, Seat, Vulnerability};
+
+macro_rules! test_declarer_score {
+    ($($name:ident: $value:expr,)*) => {
+        mod declarer_score {
+            use super::super::{declarer_score, Doubling::*};
+            use crate::Contract;
+        $(
+            #[test]
+            fn $name() {
+                let (contract, doubling, tricks, vulnerable, expected) = $value;
+                let contract = Contract::parse(contract).unwrap();
+                assert_eq!(declarer_score(contract, doubling, tricks, vulnerable), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_declarer_score! {
+	part_score_minor: ("2C", Undoubled, 8, false, 90),
+	part_score_major_overtrick: ("2S", Undoubled, 9, false, 140),
+	one_nt: ("1NT", Undoubled, 7, true, 90),
+	three_nt: ("3NT", Undoubled, 9, false, 400),
+	three_nt_vulnerable: ("3NT", Undoubled, 10, true, 630),
+	four_hearts: ("4H", Undoubled, 10, false, 420),
+	five_diamonds_vulnerable: ("5D", Undoubled, 11, true, 600),
+	doubled_into_game: ("2H", Doubled, 8, false, 470),
+	doubled_overtricks: ("2H", Doubled, 10, true, 1070),
+	redoubled_into_game: ("1C", Redoubled, 7, false, 230),
+	redoubled_overtrick: ("1NT", Redoubled, 8, true, 1160),
+	small_slam: ("6S", Undoubled, 12, false, 980),
+	small_slam_vulnerable: ("6NT", Undoubled, 13, true, 1470),
+	grand_slam: ("7C", Undoubled, 13, true, 2140),
+	doubled_grand_slam: ("7NT", Doubled, 13, true, 2490),
+	down_undoubled: ("4S", Undoubled, 8, false, -100),
+	down_undoubled_vulnerable: ("4S", Undoubled, 7, true, -300),
+	down_one_doubled: ("3NT", Doubled, 8, false, -100),
+	down_three_doubled: ("4H", Doubled, 7, false, -500),
+	down_four_doubled: ("4H", Doubled, 6, false, -800),
+	down_two_doubled_vulnerable: ("4H", Doubled, 8, true, -500),
+	down_one_redoubled: ("2D", Redoubled, 7, false, -200),
+	down_three_redoubled_vulnerable: ("5C", Redoubled, 8, true, -1600),
+	down_thirteen: ("7NT", Doubled, 0, true, -3800),
+}
+
+#[test]
+fn sides() {
+	let contract = Contract::parse("4S").unwrap();
+	let score = Score::new(
+		contract,
+		Doubling::Undoubled,
+		Seat::North,
+		10,
+		Vulnerability::EW,
+	);
+	assert_eq!(score, Score { ns: 420, ew: -420 });
+
+	let score = Score::new(
+		contract,
+		Doubling::Undoubled,
+		Seat::West,
+		10,
+		Vulnerability::EW,
+	);
+	assert_eq!(score, Score { ns: -620, ew: 620 });
+	assert_eq!(score.for_seat(Seat::East), 620);
+
+	let score = Score::new(
+		contract,
+		Doubling::Doubled,
+		Seat::East,
+		9,
+		Vulnerability::NS,
+	);
+	assert_eq!(score.for_seat(Seat::South), 100);
+}
+
+#[test]
+fn doubling_display() {
+	let contract = Contract::parse("3NT").unwrap();
+	assert_eq!(format!("{}{}", contract, Doubling::Redoubled), "3NTxx");
+}
+
+macro_rules! test_imps {
+    ($($name:ident: $value:expr,)*) => {
+        mod imps {
+            use super::super::imps;
+        $(
+            #[test]
+            fn $name() {
+                let (difference, expected) = $value;
+                assert_eq!(imps(difference), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_imps! {
+	zero: (0, 0),
+	ten: (10, 0),
+	one: (20, 1),
+	top_of_band: (40, 1),
+	overtrick_game: (450 - 420, 1),
+	game_swing: (420 + 50, 10),
+	slam_swing: (980 - 480, 11),
+	negative: (-620, -12),
+	just_below: (1990, 18),
+	huge: (7600, 24),
+}
+
+#[test]
+fn grade_against_par() {
+	let table = Table::from_tricks([
+		[6, 10, 4, 3, 3],
+		[5, 3, 8, 8, 8],
+		[6, 10, 4, 3, 3],
+		[5, 3, 8, 8, 8],
+	]);
+	let par = Par::new(&table, Seat::North, Vulnerability::Neither);
+	// stopping in a part score
+	let contract = Contract::parse("2S").unwrap();
+	let score = Score::new(
+		contract,
+		Doubling::Undoubled,
+		Seat::North,
+		10,
+		Vulnerability::Neither,
+	);
+	assert_eq!(imps(score.ns - par.score), -6);
+}
+
+#[test]
+fn matchpoints() {
+	let field = [420, 450, 170, 420, -50];
+	let mp = Matchpoints::new(420, &field);
+	assert_eq!(
+		mp,
+		Matchpoints {
+			points: 3.0,
+			top: 5.0
+		}
+	);
+	assert_eq!(mp.percentage(), 60.0);
+	assert_eq!(mp.opponents().points, 2.0);
+	assert_eq!(Matchpoints::new(480, &field).percentage(), 100.0);
+	assert_eq!(Matchpoints::new(0, &[]).percentage(), 50.0);
+}
+
+#[test]
+fn neuberg() {
+	// a top on a board played four times, scaled to a field of six
+	let mp = Matchpoints::new(620, &[420, 170, -100]).neuberg(6);
+	assert_eq!(
+		mp,
+		Matchpoints {
+			points: 4.75,
+			top: 5.0
+		}
+	);
+
+	// average stays average
+	let mp = Matchpoints::new(420, &[620, 170]).neuberg(7);
+	assert_eq!(mp.percentage(), 50.0);
+
+	// a full field is unchanged
+	let mp = Matchpoints::new(420, &[620, 170, 420]);
+	assert_eq!(mp.neuberg(4), mp);
+}
@@ -0,0 +1,279 @@
+//! Hand shapes: the pattern of suit lengths, the exact shape suit by suit,
+//! broad classes of shape, and patterns for matching shapes.
+//!
+//! Following the usual bridge notation, lengths separated by `-` are a
+//! pattern in any order of suits and lengths separated by `=` are an exact
+//! shape in spade-heart-diamond-club order. In a pattern each length may be
+//! followed by `+` for that length or longer and by the suit it must be, one
+//! of `S`, `H`, `D`, `C`, `M` for a major or `m` for a minor, and `any` or
+//! `x` match any length. So `5+M-4m-any` is a five-card or longer major with
+//! exactly four cards in a minor, and `4=4=x=x` is four cards in each major.
+
+use super::{Dist, Error, Result, Suit};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+// spade-heart-diamond-club order, as in an exact shape
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShapeClass {
+	// 4-3-3-3, 4-4-3-2 and 5-3-3-2
+	Balanced,
+	// 5-4-2-2 and 6-3-2-2
+	SemiBalanced,
+	// one suit of five or more and no other of four
+	SingleSuited,
+	// two suits of four or more, one of them at least five
+	TwoSuited,
+	// three suits of four or more
+	ThreeSuited,
+	// a suit of eight or more, or two long suits of twelve cards together
+	Freak,
+}
+
+impl fmt::Display for ShapeClass {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let s = match self {
+			ShapeClass::Balanced => "balanced",
+			ShapeClass::SemiBalanced => "semi-balanced",
+			ShapeClass::SingleSuited => "single-suited",
+			ShapeClass::TwoSuited => "two-suited",
+			ShapeClass::ThreeSuited => "three-suited",
+			ShapeClass::Freak => "freak",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+impl Dist {
+	// the lengths in spade-heart-diamond-club order
+	pub fn lengths(&self) -> [usize; 4] {
+		[self.spades, self.hearts, self.diamonds, self.clubs]
+	}
+
+	// the lengths from longest to shortest
+	pub fn sorted(&self) -> [usize; 4] {
+		let mut lengths = self.lengths();
+		lengths.sort_unstable_by(|a, b| b.cmp(a));
+		lengths
+	}
+
+	// the pattern of lengths, such as 4-4-3-2
+	pub fn pattern(&self) -> String {
+		join(&self.sorted(), "-")
+	}
+
+	// the exact shape, such as 3=4=4=2
+	pub fn shape(&self) -> String {
+		join(&self.lengths(), "=")
+	}
+
+	pub fn class(&self) -> ShapeClass {
+		let [first, second, third, fourth] = self.sorted();
+		if self.is_balanced() {
+			ShapeClass::Balanced
+		} else if fourth == 2 && third == 2 && first <= 6 {
+			ShapeClass::SemiBalanced
+		} else if first >= 8 || first + second >= 12 {
+			ShapeClass::Freak
+		} else if third >= 4 {
+			ShapeClass::ThreeSuited
+		} else if second >= 4 {
+			ShapeClass::TwoSuited
+		} else {
+			ShapeClass::SingleSuited
+		}
+	}
+
+	pub fn matches(&self, pattern: &DistPattern) -> bool {
+		pattern.matches(self)
+	}
+}
+
+impl fmt::Display for Dist {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.shape())
+	}
+}
+
+fn join(lengths: &[usize; 4], sep: &str) -> String {
+	lengths
+		.iter()
+		.map(|l| l.to_string())
+		.collect::<Vec<String>>()
+		.join(sep)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum SuitSpec {
+	Any,
+	Major,
+	Minor,
+	Suit(Suit),
+}
+
+impl SuitSpec {
+	fn allows(self, suit: Suit) -> bool {
+		match self {
+			SuitSpec::Any => true,
+			SuitSpec::Major => suit == Suit::Spades || suit == Suit::Hearts,
+			SuitSpec::Minor => suit == Suit::Diamonds || suit == Suit::Clubs,
+			SuitSpec::Suit(s) => s == suit,
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Component {
+	// none for any length
+	length: Option<usize>,
+	or_longer: bool,
+	suit: SuitSpec,
+}
+
+impl Component {
+	fn parse(s: &str) -> Result<Self> {
+		if s == "any" || s == "x" {
+			return Ok(Component {
+				length: None,
+				or_longer: false,
+				suit: SuitSpec::Any,
+			});
+		}
+		let digits: String = s.chars().take_while(|c| c.is_ascii_digit()).collect();
+		let length = digits
+			.parse::<usize>()
+			.map_err(|_| Error::parse(format!("expected a suit length in '{}'", s)))?;
+		if length > 13 {
+			return Err(Error::parse(format!("invalid suit length in '{}'", s)));
+		}
+		let mut rest = &s[digits.len()..];
+		let or_longer = rest.starts_with('+');
+		if or_longer {
+			rest = &rest[1..];
+		}
+		let suit = match rest {
+			"" => SuitSpec::Any,
+			"M" => SuitSpec::Major,
+			"m" => SuitSpec::Minor,
+			"S" => SuitSpec::Suit(Suit::Spades),
+			"H" => SuitSpec::Suit(Suit::Hearts),
+			"D" => SuitSpec::Suit(Suit::Diamonds),
+			"C" => SuitSpec::Suit(Suit::Clubs),
+			_ => return Err(Error::parse(format!("invalid suit in '{}'", s))),
+		};
+		Ok(Component {
+			length: Some(length),
+			or_longer,
+			suit,
+		})
+	}
+
+	fn fits(&self, suit: Suit, length: usize) -> bool {
+		let long_enough = match self.length {
+			None => true,
+			Some(l) if self.or_longer => length >= l,
+			Some(l) => length == l,
+		};
+		long_enough && self.suit.allows(suit)
+	}
+}
+
+impl fmt::Display for Component {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let length = match self.length {
+			None => return write!(f, "any"),
+			Some(length) => length,
+		};
+		write!(f, "{}", length)?;
+		if self.or_longer {
+			write!(f, "+")?;
+		}
+		match self.suit {
+			SuitSpec::Any => Ok(()),
+			SuitSpec::Major => write!(f, "M"),
+			SuitSpec::Minor => write!(f, "m"),
+			SuitSpec::Suit(suit) => write!(f, "{}", suit),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistPattern {
+	components: Vec<Component>,
+	// an exact shape, with the components in suit order
+	exact: bool,
+}
+
+impl DistPattern {
+	pub fn parse(s: &str) -> Result<Self> {
+		let exact = s.contains('=');
+		let sep = if exact { '=' } else { '-' };
+		let components = s
+			.split(sep)
+			.map(|c| Component::parse(c.trim()))
+			.collect::<Result<Vec<Component>>>()?;
+		if components.len() > 4 || (exact && components.len() != 4) {
+			return Err(Error::parse(format!(
+				"shape pattern '{}' has the wrong number of suits",
+				s
+			)));
+		}
+		if exact && components.iter().any(|c| c.suit != SuitSpec::Any) {
+			return Err(Error::parse(format!(
+				"exact shape '{}' cannot name suits",
+				s
+			)));
+		}
+		let least: usize = components.iter().filter_map(|c| c.length).sum();
+		let open = components.iter().any(|c| c.length.is_none() || c.or_longer);
+		if least > 13 || (components.len() == 4 && !open && least != 13) {
+			return Err(Error::parse(format!("shape '{}' is impossible", s)));
+		}
+		Ok(DistPattern { components, exact })
+	}
+
+	pub fn matches(&self, dist: &Dist) -> bool {
+		let lengths = dist.lengths();
+		if self.exact {
+			return self
+				.components
+				.iter()
+				.zip(SUITS.iter().zip(&lengths))
+				.all(|(c, (suit, len))| c.fits(*suit, *len));
+		}
+		// each component takes a different suit
+		let mut used = [false; 4];
+		self.assign(0, &lengths, &mut used)
+	}
+
+	fn assign(&self, idx: usize, lengths: &[usize; 4], used: &mut [bool; 4]) -> bool {
+		let component = match self.components.get(idx) {
+			Some(component) => component,
+			None => return true,
+		};
+		for s in 0..4 {
+			if used[s] || !component.fits(SUITS[s], lengths[s]) {
+				continue;
+			}
+			used[s] = true;
+			let found = self.assign(idx + 1, lengths, used);
+			used[s] = false;
+			if found {
+				return true;
+			}
+		}
+		false
+	}
+}
+
+impl fmt::Display for DistPattern {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let sep = if self.exact { "=" } else { "-" };
+		let components: Vec<String> = self.components.iter().map(|c| c.to_string()).collect();
+		write!(f, "{}", components.join(sep))
+	}
+}
@@ -0,0 +1,119 @@
+use super::{DistPattern, ShapeClass};
+use crate::{Dist, Hand};
+
+fn dist(hand: &str) -> Dist {
+	Hand::parse_partial(hand).unwrap().distribution()
+}
+
+#[test]
+fn pattern_and_shape() {
+	let dist = dist("432|AKQJ|T987|65");
+	assert_eq!(dist.lengths(), [3, 4, 4, 2]);
+	assert_eq!(dist.sorted(), [4, 4, 3, 2]);
+	assert_eq!(dist.pattern(), "4-4-3-2");
+	assert_eq!(dist.shape(), "3=4=4=2");
+	assert_eq!(dist.to_string(), "3=4=4=2");
+}
+
+macro_rules! test_shape_class {
+    ($($name:ident: $value:expr,)*) => {
+        mod shape_class {
+            use super::super::ShapeClass::*;
+        $(
+            #[test]
+            fn $name() {
+                let (hand, expected) = $value;
+                assert_eq!(super::dist(hand).class(), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_shape_class! {
+	balanced_4333: ("AKQJ|432|432|432", Balanced),
+	balanced_4432: ("AKQJ|5432|432|32", Balanced),
+	balanced_5332: ("AKQJ2|432|432|32", Balanced),
+	semi_balanced_5422: ("AKQJ2|5432|32|32", SemiBalanced),
+	semi_balanced_6322: ("AKQJ32|432|32|32", SemiBalanced),
+	single_suited_6331: ("AKQJ32|432|432|2", SingleSuited),
+	single_suited_7222: ("AKQJ432|32|32|32", SingleSuited),
+	two_suited_5431: ("AKQJ2|5432|432|2", TwoSuited),
+	two_suited_5530: ("AKQJ2|65432|432|", TwoSuited),
+	two_suited_6511: ("AKQJ32|65432|2|2", TwoSuited),
+	three_suited_4441: ("AKQJ|5432|5432|2", ThreeSuited),
+	three_suited_5440: ("AKQJ2|5432|5432|", ThreeSuited),
+	freak_8311: ("AKQJ5432|432|2|2", Freak),
+	freak_6610: ("AKQJ32|765432|2|", Freak),
+	freak_7510: ("AKQJ432|65432|2|", Freak),
+}
+
+#[test]
+fn class_names() {
+	assert_eq!(ShapeClass::SemiBalanced.to_string(), "semi-balanced");
+}
+
+macro_rules! test_pattern_matches {
+    ($($name:ident: $value:expr,)*) => {
+        mod pattern_matches {
+            use super::super::DistPattern;
+        $(
+            #[test]
+            fn $name() {
+                let (pattern, hand, expected) = $value;
+                let pattern = DistPattern::parse(pattern).unwrap();
+                assert_eq!(super::dist(hand).matches(&pattern), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_pattern_matches! {
+	pattern_any_order: ("4-4-3-2", "32|AKQJ|T987|654", true),
+	pattern_wrong_lengths: ("4-4-3-2", "32|AKQJ2|T987|65", false),
+	or_longer: ("5+-4+", "AKQJ2|5432|432|2", true),
+	or_longer_too_short: ("6+-x", "AKQJ2|5432|432|2", false),
+	major_and_minor: ("5+M-4m-any", "32|AKQJ2|T987|65", true),
+	major_too_short: ("5+M-4m-any", "AKQJ|32|T9872|65", false),
+	major_used_once: ("5+M-4M", "AKQJ2|5432|432|2", true),
+	suits_not_shared: ("5+M-5+M", "AKQJ2|543|5432|2", false),
+	named_suit: ("4S-5+H", "AKQJ|65432|432|2", true),
+	exact_shape: ("3=4=4=2", "432|AKQJ|T987|65", true),
+	exact_wrong_order: ("4=3=4=2", "432|AKQJ|T987|65", false),
+	exact_wildcards: ("4=4=x=x", "AKQJ|5432|T987|", true),
+	exact_or_longer: ("5+=x=x=x", "AKQJ2|432|T98|65", true),
+	backtracks: ("4+-5+", "AKQJ|65432|432|2", true),
+}
+
+macro_rules! test_pattern_rejects {
+    ($($name:ident: $value:expr,)*) => {
+        mod pattern_rejects {
+            use super::super::DistPattern;
+        $(
+            #[test]
+            fn $name() {
+                assert!(DistPattern::parse($value).is_err());
+            }
+        )*
+        }
+    }
+}
+
+test_pattern_rejects! {
+	empty: "",
+	too_many_suits: "4-3-3-2-1",
+	too_few_exact: "4=4=5",
+	too_long: "5-5-4-3",
+	too_short: "4-3-3-2",
+	bad_suit: "5+X-4",
+	bad_length: "five-4",
+	exact_named_suit: "5S=4=2=2",
+}
+
+#[test]
+fn displays_patterns() {
+	for s in &["5+M-4m-any", "4=4=any=any", "4S-5+H", "4-4-3-2"] {
+		assert_eq!(DistPattern::parse(s).unwrap().to_string(), *s);
+	}
+}
@@ -0,0 +1,275 @@
+//! Monte Carlo simulation from one player's seat: the player's hand is fixed,
+//! the other 39 cards are dealt at random as often as asked, subject to a
+//! constraint on the other hands, and each sample is analysed double dummy.
+//! The samples are shared out between threads.
+
+use super::{
+	constraint::{Constraint, MAX_ATTEMPTS},
+	dd, seeded_rng, Contract, Deal, Error, Hand, Result, Seat, Trump,
+};
+use rand::Rng;
+use std::{fmt, thread};
+
+#[cfg(test)]
+mod tests;
+
+const DEFAULT_SAMPLES: usize = 100;
+
+pub struct Simulation {
+	seat: Seat,
+	hand: Hand,
+	dealer: Seat,
+	declarer: Seat,
+	constraint: Option<Constraint>,
+	strains: Vec<Trump>,
+	samples: usize,
+	threads: usize,
+	max_attempts: usize,
+}
+
+impl Simulation {
+	// a simulation of the hand in the seat, which deals and declares unless
+	// told otherwise
+	pub fn new(seat: Seat, hand: Hand) -> Result<Self> {
+		if hand.len() != 13 {
+			return Err(Error::bridge(format!(
+				"a hand must have 13 cards, not {}",
+				hand.len()
+			)));
+		}
+		let threads = thread::available_parallelism().map_or(1, |n| n.get());
+		Ok(Simulation {
+			seat,
+			hand,
+			dealer: seat,
+			declarer: seat,
+			constraint: None,
+			strains: dd::Table::strains().to_vec(),
+			samples: DEFAULT_SAMPLES,
+			threads,
+			max_attempts: MAX_ATTEMPTS,
+		})
+	}
+
+	// only deals meeting the constraint are sampled. It can refer to any
+	// seat, including the one with the fixed hand.
+	pub fn with_constraint(self, constraint: Constraint) -> Self {
+		Simulation {
+			constraint: Some(constraint),
+			..self
+		}
+	}
+
+	// the dealer that dealer-relative constraints are read from
+	pub fn with_dealer(self, dealer: Seat) -> Self {
+		Simulation { dealer, ..self }
+	}
+
+	pub fn with_declarer(self, declarer: Seat) -> Self {
+		Simulation { declarer, ..self }
+	}
+
+	pub fn with_strains(self, strains: &[Trump]) -> Self {
+		Simulation {
+			strains: strains.to_vec(),
+			..self
+		}
+	}
+
+	pub fn with_samples(self, samples: usize) -> Self {
+		Simulation { samples, ..self }
+	}
+
+	pub fn with_threads(self, threads: usize) -> Self {
+		Simulation {
+			threads: threads.max(1),
+			..self
+		}
+	}
+
+	// the number of deals tried for each sample before giving up on the
+	// constraint, a million by default
+	pub fn with_max_attempts(self, max_attempts: usize) -> Self {
+		Simulation {
+			max_attempts,
+			..self
+		}
+	}
+
+	// deals the other three hands at random until the constraint holds
+	pub fn sample_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Deal> {
+		let mut builder = Deal::builder()
+			.with_dealer(self.dealer)
+			.with_hand(self.seat, self.hand.clone())
+			.with_max_attempts(self.max_attempts);
+		if let Some(constraint) = &self.constraint {
+			builder = builder.with_constraint(constraint.clone());
+		}
+		builder.deal_with_rng(rng)
+	}
+
+	pub fn run(&self) -> Result<Report> {
+		let mut rng = rand::thread_rng();
+		let seeds: Vec<u64> = (0..self.threads).map(|_| rng.gen()).collect();
+		self.run_with_seeds(&seeds)
+	}
+
+	// the same seed gives the same report for the same number of samples and
+	// threads
+	pub fn run_from_seed(&self, seed: u64) -> Result<Report> {
+		let seeds: Vec<u64> = (0..self.threads as u64)
+			.map(|i| seed.wrapping_add(i))
+			.collect();
+		self.run_with_seeds(&seeds)
+	}
+
+	// one thread for each seed, each taking an even share of the samples
+	fn run_with_seeds(&self, seeds: &[u64]) -> Result<Report> {
+		let threads = seeds.len();
+		let reports: Vec<Result<Report>> = thread::scope(|scope| {
+			let handles: Vec<_> = seeds
+				.iter()
+				.enumerate()
+				.map(|(i, seed)| {
+					let samples = self.samples / threads + usize::from(i < self.samples % threads);
+					scope.spawn(move || self.simulate(samples, *seed))
+				})
+				.collect();
+			handles
+				.into_iter()
+				.map(|h| h.join().expect("a simulation thread panicked"))
+				.collect()
+		});
+		let mut total = Report::new(self.declarer, &self.strains);
+		for report in reports {
+			total.merge(&report?);
+		}
+		Ok(total)
+	}
+
+	fn simulate(&self, samples: usize, seed: u64) -> Result<Report> {
+		let mut rng = seeded_rng(seed);
+		let leader = self.declarer.lho();
+		let mut report = Report::new(self.declarer, &self.strains);
+		for _ in 0..samples {
+			let deal = self.sample_with_rng(&mut rng)?;
+			let tricks: Vec<usize> = self
+				.strains
+				.iter()
+				.map(|strain| dd::solve(&deal, *strain, leader).for_seat(self.declarer))
+				.collect();
+			report.add(&tricks);
+		}
+		Ok(report)
+	}
+}
+
+// how many tricks declarer took in each strain across the samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+	declarer: Seat,
+	samples: usize,
+	strains: Vec<Trump>,
+	// for each strain, the number of samples with each number of tricks
+	counts: Vec<[usize; 14]>,
+}
+
+impl Report {
+	fn new(declarer: Seat, strains: &[Trump]) -> Self {
+		Report {
+			declarer,
+			samples: 0,
+			strains: strains.to_vec(),
+			counts: vec![[0; 14]; strains.len()],
+		}
+	}
+
+	// adds a sample, with declarer's tricks in each strain in order
+	pub(crate) fn add(&mut self, tricks: &[usize]) {
+		for (counts, tricks) in self.counts.iter_mut().zip(tricks) {
+			counts[*tricks] += 1;
+		}
+		self.samples += 1;
+	}
+
+	fn merge(&mut self, other: &Report) {
+		for (counts, other) in self.counts.iter_mut().zip(&other.counts) {
+			for (count, other) in counts.iter_mut().zip(other.iter()) {
+				*count += other;
+			}
+		}
+		self.samples += other.samples;
+	}
+
+	pub fn declarer(&self) -> Seat {
+		self.declarer
+	}
+
+	pub fn samples(&self) -> usize {
+		self.samples
+	}
+
+	pub fn strains(&self) -> &[Trump] {
+		&self.strains
+	}
+
+	// the number of samples in which declarer took each number of tricks
+	// from 0 to 13, or none for a strain that was not simulated
+	pub fn distribution(&self, strain: Trump) -> Option<&[usize; 14]> {
+		let idx = self.strains.iter().position(|s| *s == strain)?;
+		Some(&self.counts[idx])
+	}
+
+	pub fn average_tricks(&self, strain: Trump) -> Option<f64> {
+		let counts = self.distribution(strain)?;
+		let total: usize = counts.iter().enumerate().map(|(t, n)| t * n).sum();
+		Some(total as f64 / self.samples.max(1) as f64)
+	}
+
+	// the share of samples in which the contract makes, or none if its
+	// strain was not simulated
+	pub fn probability(&self, contract: Contract) -> Option<f64> {
+		self.making(contract.1, contract.0.number())
+	}
+
+	fn making(&self, strain: Trump, level: usize) -> Option<f64> {
+		let counts = self.distribution(strain)?;
+		let making: usize = counts[level + 6..].iter().sum();
+		Some(making as f64 / self.samples.max(1) as f64)
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(
+			f,
+			"{} samples with {} declaring, percentage making:",
+			self.samples, self.declarer
+		)?;
+		writeln!(
+			f,
+			"+----+-------+-----+-----+-----+-----+-----+-----+-----+"
+		)?;
+		writeln!(
+			f,
+			"|    | Avg   | 1   | 2   | 3   | 4   | 5   | 6   | 7   |"
+		)?;
+		writeln!(
+			f,
+			"+----+-------+-----+-----+-----+-----+-----+-----+-----+"
+		)?;
+		for strain in &self.strains {
+			let average = self.average_tricks(*strain).unwrap();
+			write!(f, "| {:<3}| {:<6.2}|", strain.to_string(), average)?;
+			for level in 1..=7 {
+				let percent = self.making(*strain, level).unwrap() * 100.0;
+				write!(f, " {:<4.0}|", percent)?;
+			}
+			writeln!(f)?;
+		}
+		writeln!(
+			f,
+			"+----+-------+-----+-----+-----+-----+-----+-----+-----+"
+		)
+	}
+}
@@ -0,0 +1,104 @@
+use super::{Report, Simulation};
+use crate::{
+	constraint::{Constraint, MAX_ATTEMPTS},
+	Card, Contract, Hand, Seat, Suit, Trump,
+};
+
+fn hand(s: &str) -> Hand {
+	Hand::parse(s).unwrap()
+}
+
+#[test]
+fn rejects_incomplete_hands() {
+	let short = Hand::parse_partial("AKQ|||").unwrap();
+	assert!(Simulation::new(Seat::South, short).is_err());
+	assert!(Simulation::new(Seat::South, hand("AKQJT98765432|||")).is_ok());
+}
+
+#[test]
+fn samples_keep_the_hand_and_deal_the_rest() {
+	let fixed = hand("AKQ2|KJ4|Q93|K32");
+	let constraint = Constraint::parse("hcp(north) >= 15").unwrap();
+	let simulation = Simulation::new(Seat::South, fixed.clone())
+		.unwrap()
+		.with_constraint(constraint.clone());
+	let mut rng = crate::seeded_rng(5);
+	for _ in 0..20 {
+		let deal = simulation.sample_with_rng(&mut rng).unwrap();
+		assert_eq!(deal.south, fixed);
+		assert!(constraint.matches(&deal));
+		let mut cards: Vec<Card> = Seat::vec()
+			.into_iter()
+			.flat_map(|seat| deal.hand_for_seat(seat).cards())
+			.collect();
+		cards.sort();
+		cards.dedup();
+		assert_eq!(cards.len(), 52);
+	}
+}
+
+#[test]
+fn gives_up_on_impossible_constraint() {
+	let constraint = Constraint::parse("hcp(south) >= 20").unwrap();
+	let simulation = Simulation::new(Seat::South, hand("AKQ2|KJ4|Q93|K32"))
+		.unwrap()
+		.with_constraint(constraint)
+		.with_max_attempts(10);
+	assert!(simulation
+		.sample_with_rng(&mut crate::seeded_rng(1))
+		.is_err());
+	let simulation = Simulation::new(Seat::South, hand("AKQ2|KJ4|Q93|K32")).unwrap();
+	assert_eq!(simulation.max_attempts, MAX_ATTEMPTS);
+}
+
+#[test]
+fn report_counts_tricks() {
+	let spades = Trump::Trump(Suit::Spades);
+	let mut report = Report::new(Seat::South, &[Trump::NoTrump, spades]);
+	report.add(&[9, 10]);
+	report.add(&[8, 10]);
+	report.add(&[9, 11]);
+	report.add(&[10, 9]);
+	assert_eq!(report.samples(), 4);
+	assert_eq!(report.distribution(Trump::NoTrump).unwrap()[9], 2);
+	assert_eq!(report.average_tricks(Trump::NoTrump), Some(9.0));
+	assert_eq!(
+		report.probability(Contract::parse("3NT").unwrap()),
+		Some(0.75)
+	);
+	assert_eq!(
+		report.probability(Contract::parse("4S").unwrap()),
+		Some(0.75)
+	);
+	assert_eq!(
+		report.probability(Contract::parse("5S").unwrap()),
+		Some(0.25)
+	);
+	assert_eq!(report.probability(Contract::parse("4H").unwrap()), None);
+
+	let mut total = report.clone();
+	total.merge(&report);
+	assert_eq!(total.samples(), 8);
+	assert_eq!(
+		total.probability(Contract::parse("3NT").unwrap()),
+		Some(0.75)
+	);
+}
+
+#[test]
+fn runs_in_parallel() {
+	let spades = Trump::Trump(Suit::Spades);
+	let simulation = Simulation::new(Seat::South, hand("AKQJT98765432|||"))
+		.unwrap()
+		.with_strains(&[spades])
+		.with_samples(3)
+		.with_threads(2);
+	let report = simulation.run_from_seed(9).unwrap();
+	assert_eq!(report.samples(), 3);
+	assert_eq!(report.distribution(spades).unwrap()[13], 3);
+	assert_eq!(
+		report.probability(Contract::parse("7S").unwrap()),
+		Some(1.0)
+	);
+	assert!(report.to_string().contains("| S  | 13.00 | 100 "));
+}
@@ -0,0 +1,168 @@
+//! Conditions on the bidder's hand, written as a comma-separated list such as
+//! `hcp 15-17, balanced` or `hcp 12+, spades 5+, spades >= hearts`.
+
+use super::{parse_error, Result};
+use bridge_core::{Hand, Suit};
+use std::fmt;
+
+pub const MAX_HCP: usize = 37;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Range {
+	pub min: usize,
+	pub max: usize,
+}
+
+impl Range {
+	pub fn new(min: usize, max: usize) -> Self {
+		Range { min, max }
+	}
+
+	// a single number, `n+` or `min-max`
+	fn parse(s: &str, top: usize) -> Result<Self> {
+		let number = |n: &str| {
+			n.parse::<usize>()
+				.map_err(|_| parse_error(format!("invalid range '{}'", s)))
+		};
+		let range = if let Some(min) = s.strip_suffix('+') {
+			Range::new(number(min)?, top)
+		} else if let Some(idx) = s.find('-') {
+			Range::new(number(&s[..idx])?, number(&s[idx + 1..])?)
+		} else {
+			let n = number(s)?;
+			Range::new(n, n)
+		};
+		if range.min > range.max || range.max > top {
+			return Err(parse_error(format!("invalid range '{}'", s)));
+		}
+		Ok(range)
+	}
+
+	pub fn contains(&self, n: usize) -> bool {
+		self.min <= n && n <= self.max
+	}
+
+	// whether every number in the range is in the other
+	pub fn within(&self, other: Range) -> bool {
+		other.min <= self.min && self.max <= other.max
+	}
+
+	// the numbers in both ranges, if there are any
+	pub fn intersection(&self, other: Range) -> Option<Range> {
+		let range = Range::new(self.min.max(other.min), self.max.min(other.max));
+		if range.min > range.max {
+			None
+		} else {
+			Some(range)
+		}
+	}
+
+	// the smallest range covering both
+	pub fn hull(&self, other: Range) -> Range {
+		Range::new(self.min.min(other.min), self.max.max(other.max))
+	}
+}
+
+// writes the range as it is parsed, with `n+` for a range up to `top`
+fn write_range(f: &mut fmt::Formatter, range: Range, top: usize) -> fmt::Result {
+	if range.min == range.max {
+		write!(f, "{}", range.min)
+	} else if range.max == top {
+		write!(f, "{}+", range.min)
+	} else {
+		write!(f, "{}-{}", range.min, range.max)
+	}
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Condition {
+	Hcp(Range),
+	Length(Suit, Range),
+	Balanced(bool),
+	// the first suit is at least as long as the second
+	AtLeastAsLong(Suit, Suit),
+	// the first suit is longer than the second
+	Longer(Suit, Suit),
+}
+
+impl Condition {
+	pub fn parse_list(s: &str) -> Result<Vec<Self>> {
+		if s.trim().is_empty() {
+			return Ok(Vec::new());
+		}
+		s.split(',').map(|c| Self::parse(c.trim())).collect()
+	}
+
+	fn parse(s: &str) -> Result<Self> {
+		let words: Vec<&str> = s.split_whitespace().collect();
+		let condition = match words.as_slice() {
+			["balanced"] => Condition::Balanced(true),
+			["unbalanced"] => Condition::Balanced(false),
+			["hcp", range] => Condition::Hcp(Range::parse(range, MAX_HCP)?),
+			[suit, ">=", other] => Condition::AtLeastAsLong(parse_suit(suit)?, parse_suit(other)?),
+			[suit, ">", other] => Condition::Longer(parse_suit(suit)?, parse_suit(other)?),
+			[suit, range] => Condition::Length(parse_suit(suit)?, Range::parse(range, 13)?),
+			_ => return Err(parse_error(format!("invalid condition '{}'", s))),
+		};
+		Ok(condition)
+	}
+
+	pub fn matches(&self, hand: &Hand) -> bool {
+		let dist = hand.distribution();
+		let length = |suit: Suit| match suit {
+			Suit::Spades => dist.spades,
+			Suit::Hearts => dist.hearts,
+			Suit::Diamonds => dist.diamonds,
+			Suit::Clubs => dist.clubs,
+		};
+		match self {
+			Condition::Hcp(range) => range.contains(hand.hcp()),
+			Condition::Length(suit, range) => range.contains(length(*suit)),
+			Condition::Balanced(balanced) => dist.is_balanced() == *balanced,
+			Condition::AtLeastAsLong(suit, other) => length(*suit) >= length(*other),
+			Condition::Longer(suit, other) => length(*suit) > length(*other),
+		}
+	}
+}
+
+impl fmt::Display for Condition {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Condition::Hcp(range) => {
+				write!(f, "hcp ")?;
+				write_range(f, *range, MAX_HCP)
+			}
+			Condition::Length(suit, range) => {
+				write!(f, "{} ", suit_name(*suit))?;
+				write_range(f, *range, 13)
+			}
+			Condition::Balanced(true) => write!(f, "balanced"),
+			Condition::Balanced(false) => write!(f, "unbalanced"),
+			Condition::AtLeastAsLong(suit, other) => {
+				write!(f, "{} >= {}", suit_name(*suit), suit_name(*other))
+			}
+			Condition::Longer(suit, other) => {
+				write!(f, "{} > {}", suit_name(*suit), suit_name(*other))
+			}
+		}
+	}
+}
+
+fn parse_suit(s: &str) -> Result<Suit> {
+	match s {
+		"spades" => Ok(Suit::Spades),
+		"hearts" => Ok(Suit::Hearts),
+		"diamonds" => Ok(Suit::Diamonds),
+		"clubs" => Ok(Suit::Clubs),
+		_ => Err(parse_error(format!("invalid suit '{}'", s))),
+	}
+}
+
+pub(crate) fn suit_name(suit: Suit) -> &'static str {
+	match suit {
+		Suit::Spades => "spades",
+		Suit::Hearts => "hearts",
+		Suit::Diamonds => "diamonds",
+		Suit::Clubs => "clubs",
+	}
+}
@@ -0,0 +1,324 @@
+//! What each player has shown about their hand by the calls they made, as the
+//! rest of the table reads it from the system being played.
+//!
+//! A call is read from the rules that recommend it at that point in the
+//! auction. The caller's hand meets the conditions of one of those rules and
+//! fails the rules tried before it that recommend another call, so a pass
+//! where an opening bid was possible limits the hand as well. A rule that was
+//! failed narrows the ranges only when just one of its conditions is left
+//! open. Calls the system does not cover show nothing.
+
+use super::{
+	condition::{Condition, Range, MAX_HCP},
+	Result, Rule, System,
+};
+use bridge_core::{auction::Auction, Bid, BidSequence, Seat, Suit, Vulnerability};
+use std::fmt;
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+// the lengths of a balanced hand
+const BALANCED_LENGTH: Range = Range { min: 2, max: 5 };
+
+// lengths are kept from spades down, the reverse of `Suit as usize`
+fn suit_index(suit: Suit) -> usize {
+	3 - suit as usize
+}
+
+// removes the excluded numbers from the known range where that leaves a
+// range, or gives none if no number is left
+fn cut(known: Range, excluded: Range) -> Option<Range> {
+	if known.within(excluded) {
+		None
+	} else if excluded.min <= known.min && excluded.max >= known.min {
+		Some(Range::new(excluded.max + 1, known.max))
+	} else if excluded.max >= known.max && excluded.min <= known.max {
+		Some(Range::new(known.min, excluded.min - 1))
+	} else {
+		Some(known)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shown {
+	pub hcp: Range,
+	// the lengths of the spades, hearts, diamonds and clubs
+	pub lengths: [Range; 4],
+	pub balanced: Option<bool>,
+	// the explanations of the calls made, in order
+	pub features: Vec<String>,
+}
+
+impl Shown {
+	// nothing shown yet
+	pub fn unknown() -> Self {
+		Shown {
+			hcp: Range::new(0, MAX_HCP),
+			lengths: [Range::new(0, 13); 4],
+			balanced: None,
+			features: Vec::new(),
+		}
+	}
+
+	pub fn length(&self, suit: Suit) -> Range {
+		self.lengths[suit_index(suit)]
+	}
+
+	// the ranges shown, written as conditions on the hand
+	pub fn conditions(&self) -> Vec<Condition> {
+		let mut conditions = Vec::new();
+		if self.hcp != Range::new(0, MAX_HCP) {
+			conditions.push(Condition::Hcp(self.hcp));
+		}
+		if let Some(balanced) = self.balanced {
+			conditions.push(Condition::Balanced(balanced));
+		}
+		for suit in SUITS.iter() {
+			let length = self.length(*suit);
+			let obvious = match self.balanced {
+				Some(true) => BALANCED_LENGTH,
+				_ => Range::new(0, 13),
+			};
+			if length != obvious {
+				conditions.push(Condition::Length(*suit, length));
+			}
+		}
+		conditions
+	}
+
+	// whether every hand in the ranges shown meets the condition
+	fn implies(&self, condition: &Condition) -> bool {
+		match condition {
+			Condition::Hcp(range) => self.hcp.within(*range),
+			Condition::Length(suit, range) => self.length(*suit).within(*range),
+			Condition::Balanced(balanced) => self.balanced == Some(*balanced),
+			Condition::AtLeastAsLong(suit, other) => {
+				self.length(*suit).min >= self.length(*other).max
+			}
+			Condition::Longer(suit, other) => self.length(*suit).min > self.length(*other).max,
+		}
+	}
+
+	// the ranges narrowed to hands meeting the condition, or none if no hand
+	// in them could
+	fn restrict(&self, condition: &Condition) -> Option<Self> {
+		let mut shown = self.clone();
+		match condition {
+			Condition::Hcp(range) => shown.hcp = shown.hcp.intersection(*range)?,
+			Condition::Length(suit, range) => {
+				let i = suit_index(*suit);
+				shown.lengths[i] = shown.lengths[i].intersection(*range)?;
+			}
+			Condition::Balanced(balanced) => {
+				if shown.balanced == Some(!balanced) {
+					return None;
+				}
+				shown.balanced = Some(*balanced);
+				if *balanced {
+					for length in shown.lengths.iter_mut() {
+						*length = length.intersection(BALANCED_LENGTH)?;
+					}
+				}
+			}
+			Condition::AtLeastAsLong(suit, other) => {
+				let (i, j) = (suit_index(*suit), suit_index(*other));
+				let (long, short) = (shown.lengths[i], shown.lengths[j]);
+				shown.lengths[i] = long.intersection(Range::new(short.min, 13))?;
+				shown.lengths[j] = short.intersection(Range::new(0, long.max))?;
+			}
+			Condition::Longer(suit, other) => {
+				let (i, j) = (suit_index(*suit), suit_index(*other));
+				let (long, short) = (shown.lengths[i], shown.lengths[j]);
+				if long.max == 0 || short.min == 13 {
+					return None;
+				}
+				shown.lengths[i] = long.intersection(Range::new(short.min + 1, 13))?;
+				shown.lengths[j] = short.intersection(Range::new(0, long.max - 1))?;
+			}
+		}
+		let min: usize = shown.lengths.iter().map(|l| l.min).sum();
+		let max: usize = shown.lengths.iter().map(|l| l.max).sum();
+		if min > 13 || max < 13 {
+			return None;
+		}
+		Some(shown)
+	}
+
+	fn restrict_all(&self, conditions: &[Condition]) -> Option<Self> {
+		conditions
+			.iter()
+			.try_fold(self.clone(), |shown, c| shown.restrict(c))
+	}
+
+	// the ranges narrowed to hands failing the condition, as far as ranges
+	// can express that, or none if every hand in them meets it
+	fn exclude(&self, condition: &Condition) -> Option<Self> {
+		let mut shown = self.clone();
+		match condition {
+			Condition::Hcp(range) => shown.hcp = cut(shown.hcp, *range)?,
+			Condition::Length(suit, range) => {
+				let i = suit_index(*suit);
+				shown.lengths[i] = cut(shown.lengths[i], *range)?;
+			}
+			Condition::Balanced(balanced) => {
+				return shown.restrict(&Condition::Balanced(!balanced))
+			}
+			Condition::AtLeastAsLong(suit, other) => {
+				return shown.restrict(&Condition::Longer(*other, *suit))
+			}
+			Condition::Longer(suit, other) => {
+				return shown.restrict(&Condition::AtLeastAsLong(*other, *suit))
+			}
+		}
+		Some(shown)
+	}
+
+	// the ranges narrowed to hands failing the rule, or none if every hand in
+	// them meets it
+	fn exclude_rule(&self, rule: &Rule) -> Option<Self> {
+		if self.restrict_all(&rule.conditions).is_none() {
+			return Some(self.clone());
+		}
+		let open: Vec<&Condition> = rule
+			.conditions
+			.iter()
+			.filter(|c| !self.implies(c))
+			.collect();
+		match open.as_slice() {
+			[] => None,
+			[condition] => self.exclude(condition),
+			_ => Some(self.clone()),
+		}
+	}
+
+	// the smallest ranges covering both, with the features of both
+	fn union(&self, other: &Shown) -> Self {
+		let mut shown = self.clone();
+		shown.hcp = self.hcp.hull(other.hcp);
+		for (length, other) in shown.lengths.iter_mut().zip(other.lengths.iter()) {
+			*length = length.hull(*other);
+		}
+		if self.balanced != other.balanced {
+			shown.balanced = None;
+		}
+		for feature in &other.features {
+			if !shown.features.contains(feature) {
+				shown.features.push(feature.clone());
+			}
+		}
+		shown
+	}
+}
+
+impl fmt::Display for Shown {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let conditions: Vec<String> = self.conditions().iter().map(|c| c.to_string()).collect();
+		match (conditions.is_empty(), self.features.is_empty()) {
+			(true, true) => write!(f, "nothing"),
+			(false, true) => write!(f, "{}", conditions.join(", ")),
+			(true, false) => write!(f, "{}", self.features.join("; ")),
+			(false, false) => write!(
+				f,
+				"{} ({})",
+				conditions.join(", "),
+				self.features.join("; ")
+			),
+		}
+	}
+}
+
+// what the call shows on top of what was shown before it, or none if the
+// system does not cover it
+fn read_call(system: &System, auction: &BidSequence, before: &Shown, call: Bid) -> Option<Shown> {
+	let rules: Vec<&Rule> = system
+		.rules_for(auction)
+		.filter(|r| auction.valid_continuation(r.bid))
+		.collect();
+	let mut result: Option<Shown> = None;
+	for (i, rule) in rules.iter().enumerate().filter(|(_, r)| r.bid == call) {
+		let mut shown = match before.restrict_all(&rule.conditions) {
+			Some(shown) => shown,
+			None => continue,
+		};
+		// narrowing by one failed rule can leave another with a single open
+		// condition, so go over them until nothing changes
+		let earlier: Vec<&&Rule> = rules[..i].iter().filter(|r| r.bid != call).collect();
+		let mut consistent = true;
+		loop {
+			let previous = shown.clone();
+			for rule in &earlier {
+				match shown.exclude_rule(rule) {
+					Some(narrowed) => shown = narrowed,
+					None => consistent = false,
+				}
+			}
+			if !consistent || shown == previous {
+				break;
+			}
+		}
+		if !consistent {
+			continue;
+		}
+		if !shown.features.contains(&rule.explanation) {
+			shown.features.push(rule.explanation.clone());
+		}
+		result = Some(match result {
+			Some(result) => result.union(&shown),
+			None => shown,
+		});
+	}
+	result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference<'a> {
+	system: &'a System,
+	auction: Auction,
+	// indexed north, east, south, west
+	shown: [Shown; 4],
+}
+
+impl<'a> Inference<'a> {
+	pub fn new(system: &'a System, dealer: Seat, vulnerable: Vulnerability) -> Self {
+		Inference {
+			system,
+			auction: Auction::new(dealer, vulnerable),
+			shown: [
+				Shown::unknown(),
+				Shown::unknown(),
+				Shown::unknown(),
+				Shown::unknown(),
+			],
+		}
+	}
+
+	pub fn auction(&self) -> &Auction {
+		&self.auction
+	}
+
+	pub fn shown(&self, seat: Seat) -> &Shown {
+		&self.shown[seat.index()]
+	}
+
+	// reads the next call in the auction
+	pub fn update(&mut self, call: Bid) -> Result<()> {
+		let auction = self.auction.with_continuation(call)?;
+		let i = self.auction.next_seat().index();
+		if let Some(shown) = read_call(self.system, self.auction.bids(), &self.shown[i], call) {
+			self.shown[i] = shown;
+		}
+		self.auction = auction;
+		Ok(())
+	}
+}
+
+impl System {
+	// what each player has shown by their calls in the auction
+	pub fn infer(&self, auction: &Auction) -> Result<Inference<'_>> {
+		let mut inference = Inference::new(self, auction.dealer(), auction.vulnerable());
+		for call in auction.calls() {
+			inference.update(call.bid)?;
+		}
+		Ok(inference)
+	}
+}
@@ -0,0 +1,177 @@
+//! A bidding system described as data, and the engine that finds the bid it
+//! recommends for a hand in an auction.
+//!
+//! A system is written one rule to a line, with four fields separated by `|`:
+//! the auction the rule answers, the call it recommends, conditions on the
+//! bidder's hand (see [`condition`]) and an explanation of the call.
+//!
+//! ```text
+//! name: SAYC
+//! -        | 1NT | hcp 15-17, balanced | 15-17 points, balanced
+//! 1NT,Pass | 2C  | hcp 8+, hearts 4    | Stayman, asking for a four-card major
+//! ```
+//!
+//! The auction is written as in `BidSequence::parse`, with `-` for the start
+//! of the auction. Passes before the opening bid are ignored, so opening bids
+//! apply in every seat. Rules are tried in the order written and the first
+//! whose conditions the hand meets gives the recommendation. Blank lines and
+//! lines starting with `#` are ignored.
+//!
+//! Reading an auction the other way, [`inference`] finds what each player has
+//! shown by their calls.
+
+use bridge_core::{Bid, BidSequence, Error, Hand};
+use std::fmt;
+
+pub mod condition;
+pub mod inference;
+
+#[cfg(test)]
+mod tests;
+
+use self::condition::Condition;
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub(crate) fn parse_error<T: Into<String>>(msg: T) -> Error {
+	Error::Parse(msg.into())
+}
+
+const SAYC: &str = include_str!("../systems/sayc.txt");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+	// the auction before the call, without any passes before the opening bid
+	pub auction: Vec<Bid>,
+	pub bid: Bid,
+	pub conditions: Vec<Condition>,
+	pub explanation: String,
+}
+
+impl Rule {
+	fn parse(line: &str) -> Result<Self> {
+		let fields: Vec<&str> = line.split('|').map(|f| f.trim()).collect();
+		if fields.len() != 4 {
+			return Err(parse_error(format!(
+				"rule '{}' must have four fields separated by '|'",
+				line
+			)));
+		}
+		let auction = match fields[0] {
+			"-" => Vec::new(),
+			auction => BidSequence::parse(auction)?.bids().to_vec(),
+		};
+		if auction.first() == Some(&Bid::Pass) {
+			return Err(parse_error(format!(
+				"rule '{}' cannot start with a pass",
+				line
+			)));
+		}
+		Ok(Rule {
+			auction,
+			bid: Bid::parse(fields[1])?,
+			conditions: Condition::parse_list(fields[2])?,
+			explanation: fields[3].to_string(),
+		})
+	}
+
+	// whether the rule answers the auction so far
+	pub fn applies_to(&self, auction: &BidSequence) -> bool {
+		opening(auction) == self.auction.as_slice()
+	}
+
+	pub fn matches(&self, hand: &Hand) -> bool {
+		self.conditions.iter().all(|c| c.matches(hand))
+	}
+}
+
+impl fmt::Display for Rule {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.auction.is_empty() {
+			write!(f, "-")?;
+		} else {
+			write!(f, "{}", BidSequence::new(self.auction.clone()))?;
+		}
+		let conditions: Vec<String> = self.conditions.iter().map(|c| c.to_string()).collect();
+		write!(
+			f,
+			" | {} | {} | {}",
+			self.bid,
+			conditions.join(", "),
+			self.explanation
+		)
+	}
+}
+
+// the auction from the opening bid on
+fn opening(auction: &BidSequence) -> &[Bid] {
+	let bids = auction.bids();
+	let passes = bids.iter().take_while(|b| **b == Bid::Pass).count();
+	&bids[passes..]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendation<'a> {
+	pub bid: Bid,
+	pub rule: &'a Rule,
+	pub explanation: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct System {
+	pub name: String,
+	rules: Vec<Rule>,
+}
+
+impl System {
+	pub fn parse(s: &str) -> Result<Self> {
+		let mut name = None;
+		let mut rules = Vec::new();
+		for line in s.lines().map(|l| l.trim()) {
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+			if let Some(n) = line.strip_prefix("name:") {
+				name = Some(n.trim().to_string());
+				continue;
+			}
+			rules.push(Rule::parse(line)?);
+		}
+		match name {
+			Some(name) => Ok(System { name, rules }),
+			None => Err(parse_error("a system must have a name")),
+		}
+	}
+
+	// Standard American Yellow Card, in outline
+	pub fn sayc() -> Self {
+		Self::parse(SAYC).expect("the built-in system should parse")
+	}
+
+	pub fn rules(&self) -> &[Rule] {
+		&self.rules
+	}
+
+	// the rules answering the auction so far, in the order they are tried
+	pub fn rules_for<'a>(&'a self, auction: &'a BidSequence) -> impl Iterator<Item = &'a Rule> {
+		self.rules.iter().filter(move |r| r.applies_to(auction))
+	}
+
+	// the call the system recommends with this hand, or none if the system
+	// does not cover the auction or the hand
+	pub fn recommend<'a>(
+		&'a self,
+		hand: &Hand,
+		auction: &BidSequence,
+	) -> Option<Recommendation<'a>> {
+		self.rules
+			.iter()
+			.filter(|r| r.applies_to(auction) && auction.valid_continuation(r.bid))
+			.find(|r| r.matches(hand))
+			.map(|rule| Recommendation {
+				bid: rule.bid,
+				rule,
+				explanation: &rule.explanation,
+			})
+	}
+}
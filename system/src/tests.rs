@@ -0,0 +1,189 @@
+use super::{
+	condition::{Condition, Range},
+	inference::Inference,
+	System,
+};
+use bridge_core::{auction::Auction, Bid, BidSequence, Hand, Seat, Suit, Vulnerability};
+
+fn hand(s: &str) -> Hand {
+	Hand::parse(s).unwrap()
+}
+
+macro_rules! test_sayc_recommends {
+    ($($name:ident: $value:expr,)*) => {
+        mod sayc_recommends {
+            use super::super::System;
+            use bridge_core::{Bid, BidSequence};
+        $(
+            #[test]
+            fn $name() {
+                let (hand, auction, expected) = $value;
+                let system = System::sayc();
+                let auction = BidSequence::parse(auction).unwrap();
+                let recommendation = system.recommend(&super::hand(hand), &auction).unwrap();
+                assert_eq!(recommendation.bid, Bid::parse(expected).unwrap());
+            }
+        )*
+        }
+    }
+}
+
+test_sayc_recommends! {
+	one_nt: ("AQ3|KJ4|Q932|K32", "", "1NT"),
+	one_nt_third_seat: ("AQ3|KJ4|Q932|K32", "Pass,Pass", "1NT"),
+	one_spade_five_five: ("AQ932|KJ432|Q2|2", "", "1S"),
+	one_heart: ("A2|KJ432|Q32|K32", "", "1H"),
+	one_diamond_four_four: ("A2|K32|QJ32|KJ32", "", "1D"),
+	one_club_three_three: ("A32|K432|QJ2|KJ2", "", "1C"),
+	strong_two_clubs: ("AKQ2|AKQ2|AQ2|K2", "", "2C"),
+	weak_two: ("KQJ932|432|32|32", "", "2S"),
+	pass_opening: ("K32|432|5432|Q32", "", "Pass"),
+	limit_raise: ("K432|A32|Q432|K2", "1S,Pass", "3S"),
+	one_over_one: ("K432|32|Q432|A32", "1H,Pass", "1S"),
+	stayman: ("K432|A32|Q432|32", "1NT,Pass", "2C"),
+	transfer: ("K5432|32|Q432|32", "1NT,Pass", "2H"),
+	stayman_answer: ("AQ3|KJ43|Q93|K32", "1NT,Pass,2C,Pass", "2H"),
+	stayman_denial: ("AQ3|KJ4|Q932|K32", "1NT,Pass,2C,Pass", "2D"),
+	complete_transfer: ("AQ3|KJ4|Q932|K32", "1NT,Pass,2D,Pass", "2H"),
+}
+
+#[test]
+fn explains_recommendation() {
+	let system = System::sayc();
+	let auction = BidSequence::parse("1NT,Pass").unwrap();
+	let recommendation = system
+		.recommend(&hand("K432|A32|Q432|32"), &auction)
+		.unwrap();
+	assert_eq!(
+		recommendation.explanation,
+		"Stayman, asking for a four-card major"
+	);
+	assert_eq!(
+		recommendation.rule.conditions,
+		Condition::parse_list("hcp 8+, spades 4").unwrap()
+	);
+}
+
+#[test]
+fn uncovered_auction() {
+	let system = System::sayc();
+	let auction = BidSequence::parse("1C,2D").unwrap();
+	assert!(system
+		.recommend(&hand("AQ3|KJ4|Q932|K32"), &auction)
+		.is_none());
+}
+
+#[test]
+fn skips_illegal_bids() {
+	let system = System::parse(
+		"name: test\n\
+		 1S | 1H | | too low\n\
+		 1S | 2H | | legal",
+	)
+	.unwrap();
+	let auction = BidSequence::parse("1S").unwrap();
+	let recommendation = system.recommend(&hand("AQ3|KJ4|Q932|K32"), &auction);
+	assert_eq!(recommendation.unwrap().bid, Bid::parse("2H").unwrap());
+}
+
+#[test]
+fn rules_display_as_written() {
+	let system = System::parse("name: test\n1NT,Pass | 2C | hcp 8+, hearts 4 | Stayman").unwrap();
+	assert_eq!(
+		system.rules()[0].to_string(),
+		"1NT,Pass | 2C | hcp 8+, hearts 4 | Stayman"
+	);
+}
+
+macro_rules! test_system_rejects {
+    ($($name:ident: $value:expr,)*) => {
+        mod system_rejects {
+            use super::super::System;
+        $(
+            #[test]
+            fn $name() {
+                assert!(System::parse($value).is_err());
+            }
+        )*
+        }
+    }
+}
+
+test_system_rejects! {
+	no_name: "- | 1NT | hcp 15-17 | strong",
+	missing_field: "name: x\n- | 1NT | hcp 15-17",
+	bad_bid: "name: x\n- | 1Z | hcp 15-17 | strong",
+	bad_auction: "name: x\n1X | 1NT | hcp 15-17 | strong",
+	leading_pass: "name: x\nPass | 1NT | hcp 15-17 | strong",
+	bad_condition: "name: x\n- | 1NT | points 15-17 | strong",
+	bad_range: "name: x\n- | 1NT | hcp 17-15 | strong",
+	long_suit: "name: x\n- | 1S | spades 14 | long",
+}
+
+fn shown(auction: &str, dealer: Seat, seat: Seat) -> String {
+	let system = System::sayc();
+	let auction = Auction::parse(auction, dealer, Vulnerability::Neither).unwrap();
+	let inference = system.infer(&auction).unwrap();
+	inference.shown(seat).to_string()
+}
+
+macro_rules! test_sayc_shows {
+    ($($name:ident: $value:expr,)*) => {
+        mod sayc_shows {
+            use bridge_core::Seat::*;
+        $(
+            #[test]
+            fn $name() {
+                let (auction, dealer, seat, expected) = $value;
+                assert_eq!(super::shown(auction, dealer, seat), expected);
+            }
+        )*
+        }
+    }
+}
+
+test_sayc_shows! {
+	nothing_yet: ("", North, North, "nothing"),
+	one_nt: ("1NT", North, North, "hcp 15-17, balanced (15-17 points, balanced)"),
+	one_nt_by_dealer: ("Pass,1NT", West, North, "hcp 15-17, balanced (15-17 points, balanced)"),
+	passed_hand: ("Pass", South, South, "hcp 0-11 (not enough to open)"),
+	one_club_denies_five_hearts: ("1C", North, North, "hcp 12-21, hearts 0-4 (three or more clubs)"),
+	one_spade: ("1S", East, East, "hcp 12-21, spades 5+ (five or more spades)"),
+	weak_two: ("Pass,2H", North, East, "hcp 5-11, hearts 6 (weak two, six hearts)"),
+	stayman: ("1NT,Pass,2C", North, South, "hcp 8+, hearts 0-4 (Stayman, asking for a four-card major)"),
+	stayman_answer: (
+		"1NT,Pass,2C,Pass,2H",
+		North,
+		North,
+		"hcp 15-17, balanced, hearts 4-5 (15-17 points, balanced; four hearts, perhaps four spades as well)"
+	),
+	transfer: ("1NT,Pass,2H", North, South, "spades 5+ (transfer to spades)"),
+	response_pass: ("1S,Pass,Pass", North, South, "hcp 0-5 (not enough to respond)"),
+	opponent_pass: ("1NT,Pass", North, East, "nothing"),
+	uncovered_call: ("1C,2D", North, East, "nothing"),
+}
+
+#[test]
+fn inference_updates_call_by_call() {
+	let system = System::sayc();
+	let mut inference = Inference::new(&system, Seat::North, Vulnerability::Neither);
+	inference.update(Bid::parse("1NT").unwrap()).unwrap();
+	let north = inference.shown(Seat::North).clone();
+	assert_eq!(north.hcp, Range::new(15, 17));
+	assert_eq!(north.length(Suit::Hearts), Range::new(2, 5));
+	inference.update(Bid::Pass).unwrap();
+	inference.update(Bid::parse("2C").unwrap()).unwrap();
+	inference.update(Bid::Pass).unwrap();
+	assert_eq!(inference.shown(Seat::North), &north);
+	inference.update(Bid::parse("2S").unwrap()).unwrap();
+	assert_eq!(
+		inference.shown(Seat::North).length(Suit::Spades),
+		Range::new(4, 5)
+	);
+	assert_eq!(
+		inference.auction().bids().to_string(),
+		"1NT,Pass,2C,Pass,2S"
+	);
+	assert_eq!(inference.auction().next_seat(), Seat::East);
+	assert!(inference.update(Bid::parse("1S").unwrap()).is_err());
+}